@@ -54,11 +54,11 @@ impl std::fmt::Display for CalcToken {
 
 //a TextPos, TextStream
 //tp TextPos
-///
+/// The position type used by the calculator lexer - byte offset, line and column
 type TextPos = StreamCharPos<LineColumn>;
 
 //tp TextStream
-///
+/// The lexer of a borrowed str producing calculator tokens
 type TextStream<'a> = LexerOfStr<'a, TextPos, CalcToken, SimpleParseError<TextPos>>;
 
 //a CalcLexResult
@@ -91,7 +91,7 @@ fn parse_char_fn(stream: &TextStream, state: TextPos, ch: char) -> CalcLexResult
 //fi parse_value_fn
 /// Parser function to return a Token if the text matches a value
 fn parse_value_fn(stream: &TextStream, state: TextPos, ch: char) -> CalcLexResult {
-    let is_digit = |_, ch| ('0'..='9').contains(&ch);
+    let is_digit = |_, ch: char| ch.is_ascii_digit();
     let (state, opt_x) = stream.do_while(state, ch, &is_digit);
     if let Some((start, _n)) = opt_x {
         let s = stream.get_text(start, state);
@@ -122,15 +122,15 @@ struct CalcTokenParser<'a> {
 }
 impl<'a> CalcTokenParser<'a> {
     pub fn new() -> Self {
-        let mut parsers = Vec::new();
-
         // Note that we use 'as BoxDynCalcLexFn' because type inference kicks in for the Box::new()
         // and does not let parse_value_fn get correctly inferred as dyn Fn(...)
         //
         // Forcing it this way kicks the type inference
-        parsers.push(Box::new(parse_value_fn) as BoxDynCalcLexFn);
-        parsers.push(Box::new(parse_char_fn) as BoxDynCalcLexFn);
-        parsers.push(Box::new(parse_whitespace_fn) as BoxDynCalcLexFn);
+        let parsers = vec![
+            Box::new(parse_value_fn) as BoxDynCalcLexFn,
+            Box::new(parse_char_fn) as BoxDynCalcLexFn,
+            Box::new(parse_whitespace_fn) as BoxDynCalcLexFn,
+        ];
         Self { parsers }
     }
     /*
@@ -185,11 +185,14 @@ fn main() -> Result<(), String> {
 //a Tests - run with cargo test --examples
 #[test]
 fn test_lex_0() {
+    use lexer_rs::{LexerTokenStream, ParserInputStream};
+    let c = CalcTokenParser::new();
     let ts = TextStream::new("1+3");
+    let ts = LexerTokenStream::new(&ts, &c.parsers);
     let (ts, t) = ts.get_token().unwrap().unwrap();
     assert_eq!(t, CalcToken::Value(1.0));
     let (ts, t) = ts.get_token().unwrap().unwrap();
-    assert_eq!(t, CalcToken::Op(Op::Plus));
+    assert_eq!(t, CalcToken::Op(CalcOp::Plus));
     let (ts, t) = ts.get_token().unwrap().unwrap();
     assert_eq!(t, CalcToken::Value(3.0));
     let x = ts.get_token().unwrap();
@@ -198,17 +201,40 @@ fn test_lex_0() {
 
 #[test]
 fn test_lex_1() {
-    let mut ts = TextStream::new("2() \t-\n*+/");
+    use lexer_rs::{LexerTokenStream, ParserInputStream};
+    let c = CalcTokenParser::new();
+    let ts = TextStream::new("2() \t-\n*+/");
+    let mut ts = LexerTokenStream::new(&ts, &c.parsers);
     for exp_t in [
         CalcToken::Value(2.0),
         CalcToken::Open,
         CalcToken::Close,
         CalcToken::Whitespace,
-        CalcToken::Op(Op::Minus),
+        CalcToken::Op(CalcOp::Minus),
         CalcToken::Whitespace,
-        CalcToken::Op(Op::Times),
-        CalcToken::Op(Op::Plus),
-        CalcToken::Op(Op::Divide),
+        CalcToken::Op(CalcOp::Times),
+        CalcToken::Op(CalcOp::Plus),
+        CalcToken::Op(CalcOp::Divide),
+    ] {
+        let (next_ts, t) = ts.get_token().unwrap().unwrap();
+        assert_eq!(t, exp_t);
+        ts = next_ts;
+    }
+    let x = ts.get_token().unwrap();
+    assert!(x.is_none());
+}
+
+#[test]
+fn test_lex_skip_whitespace() {
+    use lexer_rs::{LexerTokenStream, ParserInputStream};
+    let c = CalcTokenParser::new();
+    let ts = TextStream::new(" 2 *\n 3 ");
+    let is_whitespace = |t: &CalcToken| *t == CalcToken::Whitespace;
+    let mut ts = LexerTokenStream::new(&ts, &c.parsers).skip_trivia(&is_whitespace);
+    for exp_t in [
+        CalcToken::Value(2.0),
+        CalcToken::Op(CalcOp::Times),
+        CalcToken::Value(3.0),
     ] {
         let (next_ts, t) = ts.get_token().unwrap().unwrap();
         assert_eq!(t, exp_t);
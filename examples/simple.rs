@@ -107,7 +107,7 @@ where
         L: CharStream<P>,
         L: Lexer<Token = Self, State = P>,
     {
-        match stream.do_while(state, ch, &|_, ch| (ch == ' ' || ch == '\t')) {
+        match stream.do_while(state, ch, &|_, ch| ch == ' ' || ch == '\t') {
             (state, Some((start, _))) => {
                 let span = StreamCharSpan::new(start, state);
                 Ok(Some((state, SimpleToken::Whitespace(span))))
@@ -0,0 +1,367 @@
+/*a Copyright
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+  http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+
+@file    lib.rs
+@brief   Derive macro for lexer-rs token enumerations
+ */
+
+//a Documentation
+#![warn(missing_docs)]
+/*!
+
+# Derive macro for lexer-rs tokens
+
+This crate provides `#[derive(Token)]` for an enumeration of the
+tokens of a language, generating the parse functions of a
+`lexer_rs::LexerOfStr` for the tokens and a `Display` implementation.
+It is normally used through the `derive` feature of `lexer-rs`, which
+re-exports the macro as `lexer_rs::Token`.
+
+Each variant of the enumeration is given one or more attributes:
+
+* `#[token("text")]` matches the literal text; the variant must have
+  no fields
+
+* `#[regex("pattern")]` matches a pattern (in the syntax of a
+  `lexer_rs::DfaBuilder`); the variant may have no fields, or a single
+  field whose value is parsed (with `str::parse`) from the text matched
+
+* `#[skip]` marks tokens that are generally to be ignored, such as
+  whitespace or comments
+
+Either of `token` and `regex` may have a priority, as in
+`#[regex("[a-z]+", priority = 2)]`; the default priority is 1 for
+`token` and 0 for `regex`, so that keywords take precedence over
+identifiers of the same length. The longest match of any rule is
+always used.
+
+The macro generates:
+
+* an associated function `parsers()`, returning a [Vec] of the parse
+  functions (actually just one, using a `lexer_rs::Dfa` for all the
+  rules) for use with any `lexer_rs::LexerOfStr` of the token type;
+  the `Dfa` is built on the first call in a thread, and shared by the
+  parse functions of later calls in that thread
+
+* a method `is_skipped(&self)`, which is true for variants marked
+  with `#[skip]`, for use with `lexer_rs::LexerTokenStream::skip_trivia`
+  or to filter the tokens of an iterator
+
+* an implementation of `Display`, that writes the text of the first
+  `#[token]` of a variant, the value of the field of a variant with a
+  field, or else the name of the variant
+
+If the value of a field cannot be parsed from the text matched then
+the parse function returns a `failed_to_parse` error at the start of
+the text.
+
+```ignore
+#[derive(Debug, Clone, Copy, PartialEq, lexer_rs::Token)]
+enum CalcToken {
+    #[skip]
+    #[regex("[ \\t\\n]+")]
+    Whitespace,
+    #[token("(")]
+    Open,
+    #[token(")")]
+    Close,
+    #[token("+")]
+    Plus,
+    #[regex("\\d+(\\.\\d+)?")]
+    Value(f64),
+}
+```
+!*/
+
+//a Imports
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, LitInt, LitStr};
+
+//a Rule, TokenVariant
+//ti Rule
+/// A rule for a variant, from a `token` or `regex` attribute
+struct Rule {
+    /// The literal text or pattern
+    text: LitStr,
+    /// True if the text is a pattern
+    is_pattern: bool,
+    /// The priority of the rule
+    priority: usize,
+}
+
+//ti TokenVariant
+/// A variant of the token enumeration and its rules
+struct TokenVariant {
+    ident: Ident,
+    has_field: bool,
+    skip: bool,
+    rules: Vec<Rule>,
+}
+
+//fi parse_rule
+/// Parse the arguments of a `token` or `regex` attribute
+fn parse_rule(input: ParseStream, is_pattern: bool) -> syn::Result<Rule> {
+    let text: LitStr = input.parse()?;
+    let mut priority = if is_pattern { 0 } else { 1 };
+    if input.peek(syn::Token![,]) {
+        input.parse::<syn::Token![,]>()?;
+        let name: Ident = input.parse()?;
+        if name != "priority" {
+            return Err(syn::Error::new(name.span(), "expected 'priority'"));
+        }
+        input.parse::<syn::Token![=]>()?;
+        let value: LitInt = input.parse()?;
+        priority = value.base10_parse()?;
+    }
+    Ok(Rule {
+        text,
+        is_pattern,
+        priority,
+    })
+}
+
+//fi token_variants
+/// Get the variants of the enumeration with their rules
+fn token_variants(input: &DeriveInput) -> syn::Result<Vec<TokenVariant>> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "Token can only be derived for an enum",
+            ))
+        }
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "Token cannot be derived for a generic enum",
+        ));
+    }
+    let mut variants = Vec::new();
+    for v in &data.variants {
+        let has_field = match &v.fields {
+            Fields::Unit => false,
+            Fields::Unnamed(f) if f.unnamed.len() == 1 => true,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &v.fields,
+                    "a token variant must have no fields or a single unnamed field",
+                ))
+            }
+        };
+        let mut variant = TokenVariant {
+            ident: v.ident.clone(),
+            has_field,
+            skip: false,
+            rules: Vec::new(),
+        };
+        for attr in &v.attrs {
+            if attr.path().is_ident("token") {
+                if has_field {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "a #[token] variant must have no fields",
+                    ));
+                }
+                variant
+                    .rules
+                    .push(attr.parse_args_with(|i: ParseStream| parse_rule(i, false))?);
+            } else if attr.path().is_ident("regex") {
+                variant
+                    .rules
+                    .push(attr.parse_args_with(|i: ParseStream| parse_rule(i, true))?);
+            } else if attr.path().is_ident("skip") {
+                attr.meta.require_path_only()?;
+                variant.skip = true;
+            }
+        }
+        if has_field && variant.rules.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &v.ident,
+                "a token variant with a field must have a #[regex]",
+            ));
+        }
+        variants.push(variant);
+    }
+    Ok(variants)
+}
+
+//a Generation
+//fi gen_parsers
+/// Generate the `parsers` associated function
+fn gen_parsers(name: &Ident, variants: &[TokenVariant]) -> TokenStream2 {
+    let mut builder = Vec::new();
+    let mut arms = Vec::new();
+    let mut rule = 0_usize;
+    for v in variants {
+        let ident = &v.ident;
+        for r in &v.rules {
+            let text = &r.text;
+            let priority = r.priority;
+            if r.is_pattern {
+                let message = format!("invalid #[regex] pattern for {}::{}", name, ident);
+                builder.push(quote! {
+                    .pattern(#text, #priority, |_| ()).expect(#message)
+                });
+            } else {
+                builder.push(quote! {
+                    .literal(#text, #priority, |_| ())
+                });
+            }
+            if v.has_field {
+                arms.push(quote! {
+                    #rule => match text.parse() {
+                        ::std::result::Result::Ok(value) => Self::#ident(value),
+                        ::std::result::Result::Err(_) => {
+                            return ::std::result::Result::Err(E::failed_to_parse(state, ch));
+                        }
+                    },
+                });
+            } else {
+                arms.push(quote! { #rule => Self::#ident, });
+            }
+            rule += 1;
+        }
+    }
+    // The text matched and the character at its start are only
+    // required by variants with a field
+    let has_field = variants.iter().any(|v| v.has_field);
+    let (ch, text) = if has_field {
+        (
+            quote! { ch },
+            quote! { let text = ::lexer_rs::CharStream::get_text(lexer, state, end); },
+        )
+    } else {
+        (quote! { _ch }, quote! {})
+    };
+    quote! {
+        /// Get the parse functions for the tokens, for a
+        /// [::lexer_rs::LexerOfStr]
+        ///
+        /// The [::lexer_rs::Dfa] for the tokens is built on the first
+        /// call in each thread, and shared by later calls
+        pub fn parsers<'a, 's, P, E>() -> ::std::vec::Vec<
+            ::lexer_rs::BoxDynLexerParseFn<'a, ::lexer_rs::LexerOfStr<'s, P, Self, E>>,
+        >
+        where
+            P: ::lexer_rs::PosnInCharStream,
+            E: ::lexer_rs::LexerError<P>,
+        {
+            ::std::thread_local! {
+                static DFA: ::std::rc::Rc<::lexer_rs::Dfa<'static, ()>> =
+                    ::std::rc::Rc::new(::lexer_rs::DfaBuilder::new()
+                        #(#builder)*
+                        .build());
+            }
+            let dfa = DFA.with(::std::rc::Rc::clone);
+            ::std::vec![::std::boxed::Box::new(
+                move |lexer: &::lexer_rs::LexerOfStr<'s, P, Self, E>, state: P, #ch: char| {
+                    let (end, rule) = match dfa.longest_match(lexer, state) {
+                        ::std::option::Option::Some(m) => m,
+                        ::std::option::Option::None => {
+                            return ::std::result::Result::Ok(::std::option::Option::None)
+                        }
+                    };
+                    #text
+                    let token = match rule {
+                        #(#arms)*
+                        _ => ::core::unreachable!(),
+                    };
+                    ::std::result::Result::Ok(::std::option::Option::Some((end, token)))
+                },
+            )]
+        }
+    }
+}
+
+//fi gen_display
+/// Generate the implementation of `Display`
+fn gen_display(name: &Ident, variants: &[TokenVariant]) -> TokenStream2 {
+    let arms = variants.iter().map(|v| {
+        let ident = &v.ident;
+        if v.has_field {
+            quote! { Self::#ident(value) => ::std::fmt::Display::fmt(value, fmt), }
+        } else {
+            let text = v
+                .rules
+                .iter()
+                .find(|r| !r.is_pattern)
+                .map(|r| r.text.value())
+                .unwrap_or_else(|| ident.to_string());
+            quote! { Self::#ident => fmt.write_str(#text), }
+        }
+    });
+    quote! {
+        impl ::std::fmt::Display for #name {
+            fn fmt(&self, fmt: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    }
+}
+
+//a Derive
+//fp derive_token
+/// Derive the parse functions and `Display` for an enumeration of
+/// tokens with `#[token]`, `#[regex]` and `#[skip]` attributes
+///
+/// See the crate documentation for details
+#[proc_macro_derive(Token, attributes(token, regex, skip))]
+pub fn derive_token(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let variants = match token_variants(&input) {
+        Ok(variants) => variants,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let name = &input.ident;
+    let parsers = gen_parsers(name, &variants);
+    let display = gen_display(name, &variants);
+    let skipped: Vec<_> = variants
+        .iter()
+        .filter(|v| v.skip)
+        .map(|v| {
+            let ident = &v.ident;
+            if v.has_field {
+                quote! { Self::#ident(_) }
+            } else {
+                quote! { Self::#ident }
+            }
+        })
+        .collect();
+    let is_skipped = if skipped.is_empty() {
+        quote! { false }
+    } else {
+        quote! { ::core::matches!(self, #(#skipped)|*) }
+    };
+    quote! {
+        impl #name {
+            #parsers
+
+            /// Return true if the token is one that is generally to be
+            /// skipped (marked with `#[skip]`)
+            pub fn is_skipped(&self) -> bool {
+                #is_skipped
+            }
+        }
+
+        #display
+    }
+    .into()
+}
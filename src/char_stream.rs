@@ -1,7 +1,10 @@
 //a Imports
+mod context_style;
 mod fmt_context;
 mod traits;
 
 //a Exports
+pub use context_style::ContextStyle;
+pub(crate) use context_style::Paint;
 pub use fmt_context::FmtContext;
 pub use traits::CharStream;
@@ -0,0 +1,304 @@
+//a Imports
+use crate::FmtContext;
+
+//a Paint
+//tp Paint
+/// The colours used for the parts of context and diagnostics when
+/// ANSI colour output is enabled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Paint {
+    /// Line numbers and the gutter separator
+    Gutter,
+    /// Markers of errors, and the word 'error'
+    Error,
+    /// Markers of warnings, and the word 'warning'
+    Warning,
+    /// Markers of notes, and the word 'note'
+    Note,
+    /// Markers of secondary spans of a diagnostic
+    Secondary,
+    /// Emphasized text, such as the message of a diagnostic
+    Bold,
+}
+
+//ip Paint
+impl Paint {
+    //mp ansi_code
+    /// Get the ANSI SGR code for the colour
+    fn ansi_code(self) -> &'static str {
+        match self {
+            Self::Gutter => "1;34",
+            Self::Error => "1;31",
+            Self::Warning => "1;33",
+            Self::Note => "1;32",
+            Self::Secondary => "1;34",
+            Self::Bold => "1",
+        }
+    }
+}
+
+//a ContextStyle
+//tp ContextStyle
+/// Options for the formatting of context by
+/// [FmtContext::fmt_context_with] and [crate::Diagnostic::fmt_with_style]
+///
+/// The [Default] style is that used by [FmtContext::fmt_context]:
+/// one line of context before the highlighted text, a gutter of at
+/// least four characters for the line numbers, `^` markers, tabs
+/// output unchanged, and elision of the middle of long multi-line
+/// regions so that the first four and last three lines are shown.
+///
+/// A multi-line region is always followed by the line after it
+/// (unless the region ends at column 0 of a line), as
+/// [FmtContext::fmt_context] has always done; 'lines_after' adds
+/// further lines of context to this.
+///
+/// Output may be coloured with ANSI escape sequences, for display on
+/// a terminal, using [ContextStyle::with_color] or
+/// [ContextStyle::with_auto_color]; the latter honours the
+/// `NO_COLOR` environment variable. Colouring does not change the
+/// alignment of the markers with the text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextStyle {
+    /// Number of lines of context to show before the first
+    /// highlighted line
+    pub lines_before: usize,
+    /// Number of lines of context to show after the last highlighted
+    /// line, in addition to the line that always follows a multi-line
+    /// region
+    pub lines_after: usize,
+    /// Minimum width of the gutter holding the line numbers; the
+    /// gutter is widened if the line numbers require more digits
+    pub min_gutter_width: usize,
+    /// Character used to underline highlighted text
+    pub marker: char,
+    /// Character used to underline secondary spans in diagnostics
+    pub secondary_marker: char,
+    /// Tab stop width used to expand tabs to spaces in the text, so
+    /// that markers line up with it; if zero then tabs are output
+    /// unchanged, and count as a single column
+    pub tab_width: usize,
+    /// If true then the middle lines of a long multi-line region are
+    /// replaced by an ellipsis
+    pub elide_lines: bool,
+    /// Number of lines to show at the start of an elided region
+    pub elide_head: usize,
+    /// Number of lines to show at the end of an elided region
+    pub elide_tail: usize,
+    /// If true then the gutter, markers and (in diagnostics) the
+    /// severity are coloured with ANSI escape sequences
+    pub color: bool,
+}
+
+//ip Default for ContextStyle
+impl Default for ContextStyle {
+    fn default() -> Self {
+        Self {
+            lines_before: 1,
+            lines_after: 0,
+            min_gutter_width: 4,
+            marker: '^',
+            secondary_marker: '-',
+            tab_width: 0,
+            elide_lines: true,
+            elide_head: 4,
+            elide_tail: 3,
+            color: false,
+        }
+    }
+}
+
+//ip ContextStyle
+impl ContextStyle {
+    //cp with_color
+    /// Enable or disable ANSI colour output
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    //cp with_auto_color
+    /// Enable ANSI colour output unless the `NO_COLOR` environment
+    /// variable is set (to a non-empty value)
+    pub fn with_auto_color(self) -> Self {
+        let no_color = std::env::var_os("NO_COLOR").map_or(false, |v| !v.is_empty());
+        self.with_color(!no_color)
+    }
+
+    //mp gutter_width
+    /// Get the width of the gutter required for line numbers up to
+    /// `max_line`
+    pub fn gutter_width(&self, max_line: usize) -> usize {
+        let mut digits = 1;
+        let mut n = max_line;
+        while n >= 10 {
+            n /= 10;
+            digits += 1;
+        }
+        digits.max(self.min_gutter_width)
+    }
+
+    //mp last_line
+    /// Get the last line of context to show for a region ending at
+    /// `line`, with `extra` lines following it in addition to
+    /// 'lines_after', limited by the number of lines in the text
+    pub(crate) fn last_line<P, C>(&self, context: &C, line: usize, extra: usize) -> usize
+    where
+        C: FmtContext<P> + ?Sized,
+    {
+        let last_line = line + extra + self.lines_after;
+        match context.num_lines() {
+            Some(n) => last_line.min(n).max(line),
+            None => last_line,
+        }
+    }
+
+    //mp paint
+    /// Format some text, coloured if colour output is enabled
+    pub(crate) fn paint(
+        &self,
+        f: &mut dyn std::fmt::Write,
+        paint: Paint,
+        args: std::fmt::Arguments,
+    ) -> std::fmt::Result {
+        if self.color {
+            write!(f, "\x1b[{}m{}\x1b[0m", paint.ansi_code(), args)
+        } else {
+            f.write_fmt(args)
+        }
+    }
+
+    //mp fmt_gutter
+    /// Format the gutter for a line, with the line number if given
+    pub(crate) fn fmt_gutter(
+        &self,
+        f: &mut dyn std::fmt::Write,
+        width: usize,
+        line: Option<usize>,
+    ) -> std::fmt::Result {
+        match line {
+            Some(line) => self.paint(
+                f,
+                Paint::Gutter,
+                format_args!("{:>width$}|", line, width = width),
+            )?,
+            None => self.paint(
+                f,
+                Paint::Gutter,
+                format_args!("{:width$}|", "", width = width),
+            )?,
+        }
+        f.write_str("  ")
+    }
+
+    //mp fmt_separator
+    /// Format an empty gutter line
+    pub(crate) fn fmt_separator(
+        &self,
+        f: &mut dyn std::fmt::Write,
+        width: usize,
+    ) -> std::fmt::Result {
+        self.paint(
+            f,
+            Paint::Gutter,
+            format_args!("{:width$}|", "", width = width),
+        )?;
+        writeln!(f)
+    }
+
+    //mp fmt_ellipsis
+    /// Format the line that replaces elided lines
+    pub(crate) fn fmt_ellipsis(
+        &self,
+        f: &mut dyn std::fmt::Write,
+        width: usize,
+    ) -> std::fmt::Result {
+        self.paint(
+            f,
+            Paint::Gutter,
+            format_args!("{:width$}|...", "", width = width),
+        )?;
+        writeln!(f)
+    }
+
+    //mp fmt_line
+    /// Format a line of text from the context, expanding tabs if
+    /// required
+    pub(crate) fn fmt_line<P, C>(
+        &self,
+        f: &mut dyn std::fmt::Write,
+        context: &C,
+        line: usize,
+    ) -> std::fmt::Result
+    where
+        C: FmtContext<P> + ?Sized,
+    {
+        if self.tab_width == 0 {
+            return context.fmt_line(f, line);
+        }
+        let mut s = String::new();
+        context.fmt_line(&mut s, line)?;
+        let mut col = 0;
+        for ch in s.chars() {
+            if ch == '\t' {
+                let n = self.tab_width - col % self.tab_width;
+                for _ in 0..n {
+                    f.write_char(' ')?;
+                }
+                col += n;
+            } else {
+                f.write_char(ch)?;
+                col += 1;
+            }
+        }
+        Ok(())
+    }
+
+    //mp visual_column
+    /// Map a column of a line to the column at which it is output,
+    /// given the expansion of tabs
+    pub(crate) fn visual_column<P, C>(&self, context: &C, line: usize, column: usize) -> usize
+    where
+        C: FmtContext<P> + ?Sized,
+    {
+        if self.tab_width == 0 || column <= 1 {
+            return column;
+        }
+        let mut s = String::new();
+        if context.fmt_line(&mut s, line).is_err() {
+            return column;
+        }
+        let mut col = 0;
+        let mut n = 0;
+        for ch in s.chars().take(column - 1) {
+            if ch == '\t' {
+                col += self.tab_width - col % self.tab_width;
+            } else {
+                col += 1;
+            }
+            n += 1;
+        }
+        col + 1 + (column - 1 - n)
+    }
+
+    //mp fmt_marker
+    /// Format the gutter and marker characters underlining
+    /// `num_cols` columns from `first_col` (at least one marker is
+    /// output)
+    pub(crate) fn fmt_marker(
+        &self,
+        f: &mut dyn std::fmt::Write,
+        width: usize,
+        first_col: usize,
+        num_cols: usize,
+        marker: char,
+        paint: Paint,
+    ) -> std::fmt::Result {
+        self.fmt_gutter(f, width, None)?;
+        for _ in 1..first_col {
+            f.write_char(' ')?;
+        }
+        let markers = marker.to_string().repeat(num_cols.max(1));
+        self.paint(f, paint, format_args!("{}", markers))
+    }
+}
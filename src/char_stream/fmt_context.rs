@@ -1,5 +1,6 @@
 //a Imports
-use crate::UserPosn;
+use crate::char_stream::Paint;
+use crate::{ContextStyle, UserPosn};
 
 //a FmtContext
 //tt FmtContext
@@ -15,9 +16,10 @@ use crate::UserPosn;
 /// even) the lines of the text ahead of a provided span of start and
 /// end positions within the stream.
 ///
-/// Currently the format of the context is fixed - the number of lines
-/// ahead is fixed a a maximum of four, the lines are always numbered
-/// with aa line number of up to 4 digits, and so on.
+/// The format of the context produced by 'fmt_context' is that of
+/// the default [ContextStyle]; the 'fmt_context_with' method
+/// permits the number of lines of context, the gutter width, the
+/// markers, tab expansion and the elision of lines to be configured.
 pub trait FmtContext<P> {
     /// Return the length of the specified line
     fn line_length(&self, line: usize) -> usize;
@@ -28,6 +30,26 @@ pub trait FmtContext<P> {
     /// if context markers are to line up correctly
     fn fmt_line(&self, f: &mut dyn std::fmt::Write, line: usize) -> std::fmt::Result;
 
+    /// Return the number of lines in the text, if known
+    ///
+    /// This is used to limit the lines of context shown after a
+    /// region
+    fn num_lines(&self) -> Option<usize> {
+        None
+    }
+
+    /// Format a position as a location for a header of (e.g.) an
+    /// error message
+    ///
+    /// The default is `line:column`; a type that knows the name of
+    /// its file may precede this with the name
+    fn fmt_location(&self, f: &mut dyn std::fmt::Write, posn: &P) -> std::fmt::Result
+    where
+        P: UserPosn,
+    {
+        write!(f, "{}:{}", posn.line(), posn.column())
+    }
+
     /// Format a line of text with highlight on certain columns
     fn fmt_context_single_line(
         &self,
@@ -35,32 +57,54 @@ pub trait FmtContext<P> {
         start: &P,
         num_cols: usize, // number of columns to highlight
     ) -> std::fmt::Result
+    where
+        P: UserPosn,
+    {
+        self.fmt_context_single_line_with(f, &ContextStyle::default(), start, num_cols)
+    }
+
+    /// Format a line of text with highlight on certain columns, in a
+    /// given style
+    fn fmt_context_single_line_with(
+        &self,
+        f: &mut dyn std::fmt::Write,
+        style: &ContextStyle,
+        start: &P,
+        num_cols: usize, // number of columns to highlight
+    ) -> std::fmt::Result
     where
         P: UserPosn,
     {
         let line = start.line();
-        let first_col = start.column();
-        if line > 1 {
-            write!(f, "    |  ")?;
-            self.fmt_line(f, line - 1)?;
+        let first_line = line.saturating_sub(style.lines_before).max(1);
+        let last_line = style.last_line(self, line, 0);
+        let width = style.gutter_width(last_line);
+        for l in first_line..line {
+            style.fmt_gutter(f, width, None)?;
+            style.fmt_line(f, self, l)?;
             writeln!(f)?;
         }
-        write!(f, "{:4}|  ", line)?;
-        self.fmt_line(f, line)?;
+        style.fmt_gutter(f, width, Some(line))?;
+        style.fmt_line(f, self, line)?;
         writeln!(f)?;
-        write!(f, "    |  ")?;
-        for _ in 1..(first_col) {
-            f.write_char(' ')?;
-        }
-        if num_cols == 0 {
-            f.write_char('^')?;
-        } else {
-            for _ in 0..num_cols {
-                f.write_char('^')?;
-            }
-        }
+        let first_col = style.visual_column(self, line, start.column());
+        let end_col = style.visual_column(self, line, start.column() + num_cols);
+        style.fmt_marker(
+            f,
+            width,
+            first_col,
+            end_col - first_col,
+            style.marker,
+            Paint::Error,
+        )?;
         writeln!(f)?;
-        writeln!(f, "    |  ")
+        for l in (line + 1)..=last_line {
+            style.fmt_gutter(f, width, None)?;
+            style.fmt_line(f, self, l)?;
+            writeln!(f)?;
+        }
+        style.fmt_gutter(f, width, None)?;
+        writeln!(f)
     }
 
     /// Format multiple lines of text, highlighting certain lines
@@ -73,20 +117,25 @@ pub trait FmtContext<P> {
     where
         P: UserPosn,
     {
-        let first_line = start.line();
-        let first_line = if first_line > 1 {
-            first_line - 1
-        } else {
-            first_line
-        };
-        let last_line = end.line() + {
-            if end.column() == 0 {
-                0
-            } else {
-                1
-            }
-        };
-        // Change to a Range
+        self.fmt_context_multiple_lines_with(f, &ContextStyle::default(), start, end)
+    }
+
+    /// Format multiple lines of text, highlighting certain lines, in a
+    /// given style
+    fn fmt_context_multiple_lines_with(
+        &self,
+        f: &mut dyn std::fmt::Write,
+        style: &ContextStyle,
+        start: &P,
+        end: &P,
+    ) -> std::fmt::Result
+    where
+        P: UserPosn,
+    {
+        let first_line = start.line().saturating_sub(style.lines_before).max(1);
+        let following = if end.column() == 0 { 0 } else { 1 };
+        let last_line = style.last_line(self, end.line(), following);
+        let width = style.gutter_width(last_line);
         let num_lines = {
             if last_line <= first_line {
                 1
@@ -94,31 +143,24 @@ pub trait FmtContext<P> {
                 last_line + 1 - first_line
             }
         };
-
-        let (start_skip, end_skip) = {
-            if num_lines > 4 {
-                (4, num_lines - 4)
-            } else {
-                (1, 0)
-            }
-        };
+        let elide = style.elide_lines && num_lines > style.elide_head + style.elide_tail;
 
         let mut ellipses_output = false;
         for i in 0..num_lines {
             let l = first_line + i;
-            if i >= start_skip && i <= end_skip {
+            if elide && i >= style.elide_head && i < num_lines - style.elide_tail {
                 if !ellipses_output {
-                    writeln!(f, "    |...")?;
+                    style.fmt_ellipsis(f, width)?;
                     ellipses_output = true;
                 }
                 continue;
             }
             if l >= start.line() && l <= end.line() {
-                write!(f, "{:4}|  ", l)?;
+                style.fmt_gutter(f, width, Some(l))?;
             } else {
-                write!(f, "    |  ")?;
+                style.fmt_gutter(f, width, None)?;
             }
-            self.fmt_line(f, l)?;
+            style.fmt_line(f, self, l)?;
             writeln!(f)?;
         }
         Ok(())
@@ -128,20 +170,35 @@ pub trait FmtContext<P> {
     ///
     /// This is the main method used by clients of the trait
     fn fmt_context(&self, fmt: &mut dyn std::fmt::Write, start: &P, end: &P) -> std::fmt::Result
+    where
+        P: UserPosn,
+    {
+        self.fmt_context_with(fmt, &ContextStyle::default(), start, end)
+    }
+
+    /// Format text with highlighting between start and end, in a
+    /// given [ContextStyle]
+    fn fmt_context_with(
+        &self,
+        fmt: &mut dyn std::fmt::Write,
+        style: &ContextStyle,
+        start: &P,
+        end: &P,
+    ) -> std::fmt::Result
     where
         P: UserPosn,
     {
         if start.line() == end.line() || (start.line() + 1 == end.line() && end.column() == 0) {
             let num_cols = {
                 if start.line() == end.line() {
-                    end.column() - start.column()
+                    end.column().saturating_sub(start.column())
                 } else {
                     self.line_length(start.line())
                 }
             };
-            self.fmt_context_single_line(fmt, start, num_cols)
+            self.fmt_context_single_line_with(fmt, style, start, num_cols)
         } else {
-            self.fmt_context_multiple_lines(fmt, start, end)
+            self.fmt_context_multiple_lines_with(fmt, style, start, end)
         }
     }
 }
@@ -1,5 +1,5 @@
 //a Imports
-use crate::{PosnInCharStream, StreamCharSpan};
+use crate::{Interner, PosnInCharStream, StreamCharSpan};
 
 //tt CharStream
 /// The [CharStream] trait allows a stream of [char] to provide extraa methods
@@ -58,6 +58,14 @@ pub trait CharStream<P> {
     /// the state is the end of the stream
     fn peek_at(&self, state: &P) -> Option<char>;
 
+    /// Get the [Interner] of the stream, if it has one, so that parse
+    /// functions may intern the text of tokens as [crate::Symbol]s
+    ///
+    /// The default is for a stream to have no interner
+    fn interner(&self) -> Option<&Interner> {
+        None
+    }
+
     //cp consumed
     /// Move the stream state forward by the specified number of characters
     fn consumed(&self, state: P, num_chars: usize) -> P;
@@ -131,6 +139,7 @@ pub trait CharStream<P> {
     /// state earlier in the stream will be requested in the future
     ///
     /// A truly streaming source can drop earlier data in the stream
-    /// if this fits the application
+    /// if this fits the application; [crate::LexerOfReader] does so
+    /// when it is compacted
     fn commit_consumed(&self, _up_to: &P) {}
 }
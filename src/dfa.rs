@@ -0,0 +1,12 @@
+//a Imports
+mod builder;
+mod dfa_lexer;
+mod error;
+mod nfa;
+mod table;
+
+//a Exports
+pub use builder::DfaBuilder;
+pub use dfa_lexer::DfaLexer;
+pub use error::DfaError;
+pub use table::Dfa;
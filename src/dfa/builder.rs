@@ -0,0 +1,181 @@
+//a Imports
+use std::collections::{BTreeSet, HashMap};
+
+use super::nfa::Nfa;
+use super::table::{Dfa, DfaRule, DfaState, NO_STATE};
+use super::DfaError;
+
+//a DfaBuilder
+//tp DfaBuilder
+/// A builder of a [Dfa] from a set of token rules
+///
+/// Each rule is either a literal string or a pattern, with a priority
+/// and a function that creates the token from the text it matches.
+///
+/// Patterns support a subset of regular expression syntax:
+/// alternation (`a|b`), grouping (`(ab)`), repetition (`*`, `+` and
+/// `?`), character classes (`[a-z_]` and `[^0-9]`), any character
+/// except newline (`.`), the escapes `\d`, `\w`, `\s`, `\n`, `\r` and
+/// `\t`, and escaped punctuation (such as `\+`).
+///
+/// When the text matched by more than one rule is the same (such as
+/// a keyword that is also an identifier) the rule with the highest
+/// priority is used; if the priorities are equal then the rule added
+/// first is used.
+pub struct DfaBuilder<'a, T> {
+    nfa: Nfa,
+    rules: Vec<DfaRule<'a, T>>,
+}
+
+//ip Default for DfaBuilder
+impl<'a, T> Default for DfaBuilder<'a, T> {
+    fn default() -> Self {
+        Self {
+            nfa: Nfa::default(),
+            rules: Vec::new(),
+        }
+    }
+}
+
+//ip Debug for DfaBuilder
+impl<'a, T> std::fmt::Debug for DfaBuilder<'a, T> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        fmt.debug_struct("DfaBuilder")
+            .field("num_rules", &self.rules.len())
+            .finish()
+    }
+}
+
+//ip DfaBuilder
+impl<'a, T> DfaBuilder<'a, T> {
+    //fp new
+    /// Create a new [DfaBuilder] with no rules
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    //cp literal
+    /// Add a rule that matches a literal string
+    pub fn literal<F>(mut self, text: &str, priority: usize, token: F) -> Self
+    where
+        F: Fn(&str) -> T + 'a,
+    {
+        self.nfa.add_literal(text, self.rules.len());
+        self.add_rule(priority, token);
+        self
+    }
+
+    //cp pattern
+    /// Add a rule that matches a pattern
+    ///
+    /// Returns an error if the pattern is invalid
+    pub fn pattern<F>(mut self, pattern: &str, priority: usize, token: F) -> Result<Self, DfaError>
+    where
+        F: Fn(&str) -> T + 'a,
+    {
+        self.nfa.add_pattern(pattern, self.rules.len())?;
+        self.add_rule(priority, token);
+        Ok(self)
+    }
+
+    //mi add_rule
+    fn add_rule<F>(&mut self, priority: usize, token: F)
+    where
+        F: Fn(&str) -> T + 'a,
+    {
+        self.rules.push(DfaRule {
+            priority,
+            token: Box::new(token),
+        });
+    }
+
+    //mp build
+    /// Build the [Dfa] from the rules, by subset construction
+    pub fn build(self) -> Dfa<'a, T> {
+        let mut sets: Vec<Vec<usize>> = vec![self.closure([0].into_iter())];
+        let mut ids: HashMap<Vec<usize>, usize> = HashMap::new();
+        ids.insert(sets[0].clone(), 0);
+        let mut states = Vec::new();
+        while states.len() < sets.len() {
+            let set = sets[states.len()].clone();
+            let accept = self.accept(&set);
+
+            // Split the characters into ranges on which the
+            // transitions of the NFA states are uniform
+            let mut transitions = Vec::new();
+            let mut points = BTreeSet::new();
+            for s in &set {
+                for (chars, target) in &self.nfa.states[*s].transitions {
+                    for (lo, hi) in chars.ranges() {
+                        transitions.push((*lo, *hi, *target));
+                        points.insert(*lo);
+                        points.insert(*hi + 1);
+                    }
+                }
+            }
+            let points: Vec<u32> = points.into_iter().collect();
+            let mut ranges: Vec<(u32, u32, u32)> = Vec::new();
+            for w in points.windows(2) {
+                let (lo, hi) = (w[0], w[1] - 1);
+                let targets = transitions
+                    .iter()
+                    .filter(|(l, h, _)| *l <= lo && hi <= *h)
+                    .map(|(_, _, t)| *t);
+                let target_set = self.closure(targets);
+                if target_set.is_empty() {
+                    continue;
+                }
+                let id = *ids.entry(target_set.clone()).or_insert_with(|| {
+                    sets.push(target_set);
+                    sets.len() - 1
+                }) as u32;
+                match ranges.last_mut() {
+                    Some(last) if last.1 + 1 == lo && last.2 == id => last.1 = hi,
+                    _ => ranges.push((lo, hi, id)),
+                }
+            }
+
+            let mut ascii = Box::new([NO_STATE; 128]);
+            for (lo, hi, id) in &ranges {
+                for c in (*lo).min(128)..(*hi + 1).min(128) {
+                    ascii[c as usize] = *id;
+                }
+            }
+            ranges.retain(|(_, hi, _)| *hi >= 128);
+            if let Some(first) = ranges.first_mut() {
+                first.0 = first.0.max(128);
+            }
+            states.push(DfaState {
+                ascii,
+                ranges,
+                accept,
+            });
+        }
+        Dfa {
+            states,
+            rules: self.rules,
+        }
+    }
+
+    //mi closure
+    /// Get the sorted epsilon closure of some NFA states
+    fn closure<I: Iterator<Item = usize>>(&self, states: I) -> Vec<usize> {
+        let mut result = BTreeSet::new();
+        let mut pending: Vec<usize> = states.collect();
+        while let Some(s) = pending.pop() {
+            if result.insert(s) {
+                pending.extend(self.nfa.states[s].epsilons.iter().copied());
+            }
+        }
+        result.into_iter().collect()
+    }
+
+    //mi accept
+    /// Get the rule accepted by a set of NFA states - that of highest
+    /// priority, and then the first added
+    fn accept(&self, set: &[usize]) -> Option<usize> {
+        set.iter()
+            .filter_map(|s| self.nfa.states[*s].accept)
+            .min_by_key(|r| (std::cmp::Reverse(self.rules[*r].priority), *r))
+    }
+}
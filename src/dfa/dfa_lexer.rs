@@ -0,0 +1,153 @@
+//a Imports
+use crate::{BoxDynLexerParseFn, CharStream, Dfa, Interner, Lexer, LexerError, LexerOfStr};
+use crate::{LexerParseResult, ParserIterator, PosnInCharStream, StreamCharSpan};
+
+//a DfaLexer
+//tp DfaLexer
+/// A [Lexer] of a [str] that uses a [Dfa] to parse tokens
+///
+/// At each position in the text the [Dfa] is used first, matching the
+/// longest text that matches any of its rules in a single pass; if
+/// none of its rules match then the parse functions supplied to
+/// [Lexer::parse] or [Lexer::iter] are tried in turn, as for a
+/// [LexerOfStr]. Hence a [DfaLexer] may be used with an empty slice
+/// of parse functions if the [Dfa] handles all of the tokens.
+///
+/// The [DfaLexer] is also a [CharStream], so parse functions may be
+/// used with it just as with a [LexerOfStr].
+#[derive(Debug)]
+pub struct DfaLexer<'a, 'd, P, T, E>
+where
+    P: PosnInCharStream,
+{
+    text: LexerOfStr<'a, P, T, E>,
+    dfa: &'d Dfa<'d, T>,
+}
+
+//ip DfaLexer
+impl<'a, 'd, P, T, E> DfaLexer<'a, 'd, P, T, E>
+where
+    P: PosnInCharStream,
+    T: Sized + std::fmt::Debug,
+    E: LexerError<P>,
+{
+    //fp new
+    /// Create a new [DfaLexer] by borrowing a [str] and a [Dfa]
+    pub fn new(text: &'a str, dfa: &'d Dfa<'d, T>) -> Self {
+        Self {
+            text: LexerOfStr::new(text),
+            dfa,
+        }
+    }
+
+    //cp with_interner
+    /// Set the [Interner] of the lexer, which parse functions may use
+    /// through [CharStream::interner] to intern the text of tokens
+    pub fn with_interner(mut self, interner: &'a Interner) -> Self {
+        self.text = self.text.with_interner(interner);
+        self
+    }
+
+    //cp with_start
+    /// Set the position at the start of the text, from which the
+    /// iterators of the [Lexer] begin; see [LexerOfStr::with_start]
+    pub fn with_start(mut self, start: P) -> Self {
+        self.text = self.text.with_start(start);
+        self
+    }
+
+    //ap dfa
+    /// Borrow the [Dfa] used by the lexer
+    pub fn dfa(&self) -> &'d Dfa<'d, T> {
+        self.dfa
+    }
+}
+
+//a Impl Lexer, CharStream
+//ip Lexer for DfaLexer
+impl<'a, 'd, P, T, E> Lexer for DfaLexer<'a, 'd, P, T, E>
+where
+    P: PosnInCharStream,
+    T: Sized + std::fmt::Debug,
+    E: LexerError<P>,
+{
+    type Token = T;
+    type Error = E;
+    type State = P;
+
+    //mp parse
+    fn parse<'iter>(
+        &'iter self,
+        state: Self::State,
+        parsers: &[BoxDynLexerParseFn<'iter, Self>],
+    ) -> LexerParseResult<Self::State, Self::Token, Self::Error> {
+        if let Some(ch) = self.peek_at(&state) {
+            if let Some((end, rule)) = self.dfa.longest_match(self, state) {
+                let token = self.dfa.token(rule, self.get_text(state, end));
+                return Ok(Some((end, token)));
+            }
+            for p in parsers {
+                let result = p(self, state, ch)?;
+                if result.is_some() {
+                    return Ok(result);
+                }
+            }
+            return Err(E::failed_to_parse(state, ch));
+        }
+        Ok(None)
+    }
+
+    //mp start
+    fn start(&self) -> Self::State {
+        self.text.start()
+    }
+
+    //mp iter
+    fn iter<'iter>(
+        &'iter self,
+        parsers: &'iter [BoxDynLexerParseFn<'iter, Self>],
+    ) -> Box<dyn Iterator<Item = Result<T, E>> + 'iter> {
+        Box::new(ParserIterator::new(self, self.start(), parsers))
+    }
+}
+
+//ip CharStream for DfaLexer
+impl<'a, 'd, P, T, E> CharStream<P> for DfaLexer<'a, 'd, P, T, E>
+where
+    P: PosnInCharStream,
+    T: Sized + std::fmt::Debug,
+    E: LexerError<P>,
+{
+    fn range_as_bytes(&self, ofs: usize, n: usize) -> &[u8] {
+        self.text.range_as_bytes(ofs, n)
+    }
+    fn get_text_span(&self, span: &StreamCharSpan<P>) -> &str {
+        self.text.get_text_span(span)
+    }
+    fn get_text(&self, start: P, end: P) -> &str {
+        self.text.get_text(start, end)
+    }
+    fn peek_at(&self, state: &P) -> Option<char> {
+        self.text.peek_at(state)
+    }
+    fn interner(&self) -> Option<&Interner> {
+        self.text.interner()
+    }
+    fn matches_bytes(&self, state: &P, s: &[u8]) -> bool {
+        self.text.matches_bytes(state, s)
+    }
+    fn matches_str(&self, pos: &P, pat: &str) -> bool {
+        self.text.matches_str(pos, pat)
+    }
+    fn consumed(&self, state: P, n: usize) -> P {
+        self.text.consumed(state, n)
+    }
+    fn do_while<F: Fn(usize, char) -> bool>(
+        &self,
+        state: P,
+        ch: char,
+        f: &F,
+    ) -> (P, Option<(P, usize)>) {
+        self.text.do_while(state, ch, f)
+    }
+}
@@ -0,0 +1,48 @@
+//a DfaError
+//tp DfaError
+/// An error in a pattern supplied to a [crate::DfaBuilder]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DfaError {
+    pattern: String,
+    position: usize,
+    message: &'static str,
+}
+
+//ip DfaError
+impl DfaError {
+    //fp new
+    /// Create a new [DfaError] for a pattern at a byte offset
+    pub(crate) fn new(pattern: &str, position: usize, message: &'static str) -> Self {
+        Self {
+            pattern: pattern.into(),
+            position,
+            message,
+        }
+    }
+
+    //ap pattern
+    /// Get the pattern that is in error
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    //ap position
+    /// Get the byte offset within the pattern of the error
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+//ip Display for DfaError
+impl std::fmt::Display for DfaError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(
+            fmt,
+            "{} at offset {} in pattern '{}'",
+            self.message, self.position, self.pattern
+        )
+    }
+}
+
+//ip Error for DfaError
+impl std::error::Error for DfaError {}
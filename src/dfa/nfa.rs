@@ -0,0 +1,428 @@
+//a Imports
+use super::DfaError;
+
+//a Constants
+/// The largest value of a [char]
+const CHAR_MAX: u32 = char::MAX as u32;
+
+/// The first surrogate code point, which is not a [char]
+const SURROGATE_START: u32 = 0xd800;
+
+/// The last surrogate code point, which is not a [char]
+const SURROGATE_END: u32 = 0xdfff;
+
+//a CharSet
+//tp CharSet
+/// A set of characters, as a sorted list of disjoint inclusive ranges
+/// of code points
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct CharSet(Vec<(u32, u32)>);
+
+//ip CharSet
+impl CharSet {
+    //fp single
+    /// Create a set of a single character
+    pub(crate) fn single(ch: char) -> Self {
+        Self(vec![(ch as u32, ch as u32)])
+    }
+
+    //fp of_ranges
+    /// Create a set from (possibly overlapping) ranges of characters
+    fn of_ranges(ranges: &[(char, char)]) -> Self {
+        let mut set = Self::default();
+        for (lo, hi) in ranges {
+            set.add(*lo as u32, *hi as u32);
+        }
+        set
+    }
+
+    //mp single_char
+    /// Get the character of the set if it contains exactly one
+    fn single_char(&self) -> Option<char> {
+        match self.0.as_slice() {
+            [(lo, hi)] if lo == hi => char::from_u32(*lo),
+            _ => None,
+        }
+    }
+
+    //mp ranges
+    /// Get the ranges of the set
+    pub(crate) fn ranges(&self) -> &[(u32, u32)] {
+        &self.0
+    }
+
+    //mp add
+    /// Add a range to the set, merging it with existing ranges
+    fn add(&mut self, lo: u32, hi: u32) {
+        self.0.push((lo, hi));
+        self.0.sort_unstable();
+        let mut merged: Vec<(u32, u32)> = Vec::with_capacity(self.0.len());
+        for (lo, hi) in self.0.drain(..) {
+            match merged.last_mut() {
+                Some(last) if lo <= last.1.saturating_add(1) => last.1 = last.1.max(hi),
+                _ => merged.push((lo, hi)),
+            }
+        }
+        self.0 = merged;
+    }
+
+    //mp union
+    /// Add all the characters of another set to this set
+    fn union(&mut self, other: &Self) {
+        for (lo, hi) in &other.0 {
+            self.add(*lo, *hi);
+        }
+    }
+
+    //mp complement
+    /// Get the set of all characters not in this set
+    fn complement(&self) -> Self {
+        let mut result = Self::default();
+        let mut next = 0;
+        for (lo, hi) in &self.0 {
+            if *lo > next {
+                result.add(next, lo - 1);
+            }
+            next = hi + 1;
+        }
+        if next <= CHAR_MAX {
+            result.add(next, CHAR_MAX);
+        }
+        result.remove_surrogates();
+        result
+    }
+
+    //mi remove_surrogates
+    /// Remove the surrogate code points from the set
+    fn remove_surrogates(&mut self) {
+        let mut ranges = Vec::new();
+        for (lo, hi) in self.0.drain(..) {
+            if hi < SURROGATE_START || lo > SURROGATE_END {
+                ranges.push((lo, hi));
+                continue;
+            }
+            if lo < SURROGATE_START {
+                ranges.push((lo, SURROGATE_START - 1));
+            }
+            if hi > SURROGATE_END {
+                ranges.push((SURROGATE_END + 1, hi));
+            }
+        }
+        self.0 = ranges;
+    }
+}
+
+//a Nfa
+//tp NfaState
+/// A state of an [Nfa], with its epsilon transitions, character
+/// transitions, and the rule that it accepts (if any)
+#[derive(Debug, Default, Clone)]
+pub(crate) struct NfaState {
+    pub(crate) epsilons: Vec<usize>,
+    pub(crate) transitions: Vec<(CharSet, usize)>,
+    pub(crate) accept: Option<usize>,
+}
+
+//tp Nfa
+/// A nondeterministic finite automaton for a set of rules, each of
+/// which is added with an epsilon transition from the start state
+/// (state 0)
+#[derive(Debug, Clone)]
+pub(crate) struct Nfa {
+    pub(crate) states: Vec<NfaState>,
+}
+
+//ip Default for Nfa
+impl Default for Nfa {
+    fn default() -> Self {
+        Self {
+            states: vec![NfaState::default()],
+        }
+    }
+}
+
+//ip Nfa
+impl Nfa {
+    //mi new_state
+    /// Add a new state, returning its index
+    fn new_state(&mut self) -> usize {
+        self.states.push(NfaState::default());
+        self.states.len() - 1
+    }
+
+    //mi epsilon
+    /// Add an epsilon transition between two states
+    fn epsilon(&mut self, from: usize, to: usize) {
+        self.states[from].epsilons.push(to);
+    }
+
+    //mi set
+    /// Add a fragment that matches a single character of a set
+    fn set(&mut self, set: CharSet) -> (usize, usize) {
+        let start = self.new_state();
+        let end = self.new_state();
+        self.states[start].transitions.push((set, end));
+        (start, end)
+    }
+
+    //mp add_literal
+    /// Add a rule that matches a literal string
+    pub(crate) fn add_literal(&mut self, text: &str, rule: usize) {
+        let start = self.new_state();
+        let mut end = start;
+        for ch in text.chars() {
+            let next = self.new_state();
+            self.states[end]
+                .transitions
+                .push((CharSet::single(ch), next));
+            end = next;
+        }
+        self.add_rule(start, end, rule);
+    }
+
+    //mp add_pattern
+    /// Add a rule that matches a pattern
+    pub(crate) fn add_pattern(&mut self, pattern: &str, rule: usize) -> Result<(), DfaError> {
+        let mut parser = PatternParser {
+            nfa: self,
+            pattern,
+            chars: pattern.char_indices().collect(),
+            index: 0,
+        };
+        let (start, end) = parser.alternation()?;
+        if parser.index < parser.chars.len() {
+            return Err(parser.error("unexpected ')'"));
+        }
+        self.add_rule(start, end, rule);
+        Ok(())
+    }
+
+    //mi add_rule
+    /// Connect a fragment for a rule to the start state
+    fn add_rule(&mut self, start: usize, end: usize, rule: usize) {
+        self.epsilon(0, start);
+        self.states[end].accept = Some(rule);
+    }
+}
+
+//a PatternParser
+//ti PatternParser
+/// A recursive descent parser of a pattern, adding fragments to an
+/// [Nfa] as it goes
+///
+/// The grammar is:
+///
+/// ```text
+/// alternation := concatenation ('|' concatenation)*
+/// concatenation := repetition*
+/// repetition := atom ('*' | '+' | '?')*
+/// atom := '(' alternation ')' | '[' class ']' | '.' | '\' escape | char
+/// ```
+struct PatternParser<'a> {
+    nfa: &'a mut Nfa,
+    pattern: &'a str,
+    chars: Vec<(usize, char)>,
+    index: usize,
+}
+
+//ii PatternParser
+impl<'a> PatternParser<'a> {
+    //mi error
+    /// Create an error at the current position in the pattern
+    fn error(&self, message: &'static str) -> DfaError {
+        let position = self
+            .chars
+            .get(self.index)
+            .map_or(self.pattern.len(), |(i, _)| *i);
+        DfaError::new(self.pattern, position, message)
+    }
+
+    //mi peek
+    /// Peek at the next character of the pattern
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).map(|(_, ch)| *ch)
+    }
+
+    //mi next
+    /// Get the next character of the pattern
+    fn next(&mut self) -> Option<char> {
+        let ch = self.peek();
+        self.index += 1;
+        ch
+    }
+
+    //mi alternation
+    fn alternation(&mut self) -> Result<(usize, usize), DfaError> {
+        let first = self.concatenation()?;
+        if self.peek() != Some('|') {
+            return Ok(first);
+        }
+        let start = self.nfa.new_state();
+        let end = self.nfa.new_state();
+        self.nfa.epsilon(start, first.0);
+        self.nfa.epsilon(first.1, end);
+        while self.peek() == Some('|') {
+            self.next();
+            let (s, e) = self.concatenation()?;
+            self.nfa.epsilon(start, s);
+            self.nfa.epsilon(e, end);
+        }
+        Ok((start, end))
+    }
+
+    //mi concatenation
+    fn concatenation(&mut self) -> Result<(usize, usize), DfaError> {
+        let start = self.nfa.new_state();
+        let mut end = start;
+        while let Some(ch) = self.peek() {
+            if ch == '|' || ch == ')' {
+                break;
+            }
+            let (s, e) = self.repetition()?;
+            self.nfa.epsilon(end, s);
+            end = e;
+        }
+        Ok((start, end))
+    }
+
+    //mi repetition
+    fn repetition(&mut self) -> Result<(usize, usize), DfaError> {
+        let (mut start, mut end) = self.atom()?;
+        while let Some(ch) = self.peek() {
+            let (s, e) = match ch {
+                '*' | '?' => {
+                    let s = self.nfa.new_state();
+                    let e = self.nfa.new_state();
+                    self.nfa.epsilon(s, start);
+                    self.nfa.epsilon(s, e);
+                    self.nfa.epsilon(end, e);
+                    if ch == '*' {
+                        self.nfa.epsilon(end, start);
+                    }
+                    (s, e)
+                }
+                '+' => {
+                    let e = self.nfa.new_state();
+                    self.nfa.epsilon(end, start);
+                    self.nfa.epsilon(end, e);
+                    (start, e)
+                }
+                _ => break,
+            };
+            self.next();
+            start = s;
+            end = e;
+        }
+        Ok((start, end))
+    }
+
+    //mi atom
+    fn atom(&mut self) -> Result<(usize, usize), DfaError> {
+        match self.next() {
+            Some('(') => {
+                let fragment = self.alternation()?;
+                if self.next() != Some(')') {
+                    self.index -= 1;
+                    return Err(self.error("missing ')'"));
+                }
+                Ok(fragment)
+            }
+            Some('[') => {
+                let set = self.class()?;
+                Ok(self.nfa.set(set))
+            }
+            Some('.') => Ok(self.nfa.set(CharSet::single('\n').complement())),
+            Some('\\') => {
+                let set = self.escape()?;
+                Ok(self.nfa.set(set))
+            }
+            Some('*') | Some('+') | Some('?') => {
+                self.index -= 1;
+                Err(self.error("repetition without an expression"))
+            }
+            Some(ch) => Ok(self.nfa.set(CharSet::single(ch))),
+            None => Err(self.error("unexpected end of pattern")),
+        }
+    }
+
+    //mi escape
+    /// Parse the character after a '\', returning the set it matches
+    fn escape(&mut self) -> Result<CharSet, DfaError> {
+        match self.next() {
+            Some('d') => Ok(CharSet::of_ranges(&[('0', '9')])),
+            Some('w') => Ok(CharSet::of_ranges(&[
+                ('0', '9'),
+                ('A', 'Z'),
+                ('_', '_'),
+                ('a', 'z'),
+            ])),
+            Some('s') => Ok(CharSet::of_ranges(&[('\t', '\r'), (' ', ' ')])),
+            Some('n') => Ok(CharSet::single('\n')),
+            Some('r') => Ok(CharSet::single('\r')),
+            Some('t') => Ok(CharSet::single('\t')),
+            Some(ch) if !ch.is_alphanumeric() => Ok(CharSet::single(ch)),
+            Some(_) => {
+                self.index -= 1;
+                Err(self.error("unknown escape"))
+            }
+            None => Err(self.error("unexpected end of pattern")),
+        }
+    }
+
+    //mi class
+    /// Parse a character class after the '['
+    fn class(&mut self) -> Result<CharSet, DfaError> {
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.next();
+        }
+        let mut set = CharSet::default();
+        loop {
+            let lo = match self.next() {
+                Some(']') => break,
+                Some('\\') => {
+                    let escaped = self.escape()?;
+                    match escaped.single_char() {
+                        Some(ch) => ch,
+                        None => {
+                            set.union(&escaped);
+                            continue;
+                        }
+                    }
+                }
+                Some(ch) => ch,
+                None => return Err(self.error("missing ']'")),
+            };
+            let hi = {
+                if self.peek() == Some('-')
+                    && self.chars.get(self.index + 1).map(|(_, c)| *c) != Some(']')
+                {
+                    self.next();
+                    match self.next() {
+                        Some('\\') => match self.escape()?.single_char() {
+                            Some(ch) => ch,
+                            None => {
+                                self.index -= 1;
+                                return Err(self.error("invalid range in class"));
+                            }
+                        },
+                        Some(ch) => ch,
+                        None => return Err(self.error("missing ']'")),
+                    }
+                } else {
+                    lo
+                }
+            };
+            if hi < lo {
+                self.index -= 1;
+                return Err(self.error("invalid range in class"));
+            }
+            set.add(lo as u32, hi as u32);
+        }
+        if negated {
+            Ok(set.complement())
+        } else {
+            Ok(set)
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//a Imports
+use crate::{BoxDynLexerParseFn, CharStream, Lexer, PosnInCharStream};
+
+//a Constants
+/// The value in an ASCII transition table indicating no transition
+pub(crate) const NO_STATE: u32 = u32::MAX;
+
+//a DfaState, DfaRule
+//tp DfaState
+/// A state of a [Dfa]
+///
+/// Transitions on ASCII characters use a table; others use a sorted
+/// list of disjoint ranges of characters
+#[derive(Debug, Clone)]
+pub(crate) struct DfaState {
+    pub(crate) ascii: Box<[u32; 128]>,
+    pub(crate) ranges: Vec<(u32, u32, u32)>,
+    pub(crate) accept: Option<usize>,
+}
+
+//ip DfaState
+impl DfaState {
+    //mi next
+    /// Get the state to move to on a character, if any
+    fn next(&self, ch: char) -> Option<usize> {
+        let c = ch as u32;
+        let next = {
+            if c < 128 {
+                self.ascii[c as usize]
+            } else {
+                match self.ranges.binary_search_by(|(lo, hi, _)| {
+                    if *hi < c {
+                        std::cmp::Ordering::Less
+                    } else if *lo > c {
+                        std::cmp::Ordering::Greater
+                    } else {
+                        std::cmp::Ordering::Equal
+                    }
+                }) {
+                    Ok(i) => self.ranges[i].2,
+                    Err(_) => NO_STATE,
+                }
+            }
+        };
+        (next != NO_STATE).then_some(next as usize)
+    }
+}
+
+//tp DfaRule
+/// A rule of a [Dfa] - its priority and the function that creates its
+/// token from the matched text
+pub(crate) struct DfaRule<'a, T> {
+    pub(crate) priority: usize,
+    pub(crate) token: Box<dyn Fn(&str) -> T + 'a>,
+}
+
+//a Dfa
+//tp Dfa
+/// A deterministic finite automaton compiled from a set of token
+/// rules by a [crate::DfaBuilder]
+///
+/// The automaton matches the longest text at a state of a stream
+/// that matches any rule (maximal munch) in a single pass, whatever
+/// the number of rules; if more than one rule matches that text then
+/// the rule with the highest priority is used, and then the rule that
+/// was added to the builder first.
+///
+/// A [Dfa] provides a parse function for any [Lexer], with
+/// [Dfa::parse_fn]; or it may be used with a [crate::DfaLexer], which
+/// uses it to parse a [str] without any parse functions.
+pub struct Dfa<'a, T> {
+    pub(crate) states: Vec<DfaState>,
+    pub(crate) rules: Vec<DfaRule<'a, T>>,
+}
+
+//ip Debug for Dfa
+impl<'a, T> std::fmt::Debug for Dfa<'a, T> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        fmt.debug_struct("Dfa")
+            .field("num_states", &self.states.len())
+            .field("num_rules", &self.rules.len())
+            .finish()
+    }
+}
+
+//ip Dfa
+impl<'a, T> Dfa<'a, T> {
+    //ap num_states
+    /// Get the number of states in the automaton
+    pub fn num_states(&self) -> usize {
+        self.states.len()
+    }
+
+    //mp longest_match
+    /// Find the longest text at a state of a stream that matches a
+    /// rule, returning the state after the text and the index of the
+    /// rule (in the order the rules were added to the builder)
+    pub fn longest_match<L, P>(&self, lexer: &L, state: P) -> Option<(P, usize)>
+    where
+        L: CharStream<P>,
+        P: PosnInCharStream,
+    {
+        let mut dfa_state = 0;
+        let mut state = state;
+        let mut result = None;
+        while let Some(ch) = lexer.peek_at(&state) {
+            match self.states[dfa_state].next(ch) {
+                Some(next) => {
+                    dfa_state = next;
+                    state = lexer.consumed_char(state, ch);
+                    if let Some(rule) = self.states[dfa_state].accept {
+                        result = Some((state, rule));
+                    }
+                }
+                None => break,
+            }
+        }
+        result
+    }
+
+    //mp token
+    /// Create the token for a rule from the text that it matched
+    pub fn token(&self, rule: usize, text: &str) -> T {
+        (self.rules[rule].token)(text)
+    }
+
+    //mp parse_fn
+    /// Create a parse function that matches the longest text matching
+    /// any rule of the automaton, yielding the token for the rule
+    pub fn parse_fn<'p, L>(&'p self) -> BoxDynLexerParseFn<'p, L>
+    where
+        L: Lexer<Token = T> + CharStream<<L as Lexer>::State>,
+        <L as Lexer>::State: PosnInCharStream,
+    {
+        Box::new(move |lexer: &L, state, _ch| {
+            Ok(self
+                .longest_match(lexer, state)
+                .map(|(end, rule)| (end, self.token(rule, lexer.get_text(state, end)))))
+        })
+    }
+}
@@ -0,0 +1,374 @@
+//a Imports
+use crate::char_stream::Paint;
+use crate::{ContextStyle, FmtContext, SimpleParseError, SimpleParseErrorKind, UserPosn};
+
+//a Severity
+//tp Severity
+/// The severity of a [Diagnostic]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// An error, such as a failure to parse
+    Error,
+    /// A warning, which does not prevent further processing
+    Warning,
+    /// Information that is not in itself a problem
+    Note,
+}
+
+//ip Display for Severity
+impl std::fmt::Display for Severity {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            Self::Error => write!(fmt, "error"),
+            Self::Warning => write!(fmt, "warning"),
+            Self::Note => write!(fmt, "note"),
+        }
+    }
+}
+
+//ip Severity
+impl Severity {
+    //mi paint
+    /// Get the colour used for the severity and its primary markers
+    fn paint(self) -> Paint {
+        match self {
+            Self::Error => Paint::Error,
+            Self::Warning => Paint::Warning,
+            Self::Note => Paint::Note,
+        }
+    }
+}
+
+//a Label, Mark
+//ti Label
+/// A labelled span of a [Diagnostic]
+#[derive(Debug, Clone)]
+struct Label<P> {
+    start: P,
+    end: P,
+    primary: bool,
+    text: String,
+}
+
+//ti Mark
+/// An underline of columns of a single line, generated from a
+/// [Label]; the text is only given for the last line of a label
+#[derive(Debug)]
+struct Mark<'a> {
+    line: usize,
+    first_col: usize,
+    num_cols: usize,
+    primary: bool,
+    text: Option<&'a str>,
+}
+
+//ii Label
+impl<P> Label<P>
+where
+    P: UserPosn,
+{
+    //mi add_marks
+    /// Add the marks for the label to a [Vec]
+    ///
+    /// A span that ends at the start of a line is taken to end at the
+    /// end of the previous line; a span across many lines is
+    /// underlined on its first and last lines
+    fn add_marks<'a, C>(&'a self, context: &C, marks: &mut Vec<Mark<'a>>)
+    where
+        C: FmtContext<P> + ?Sized,
+    {
+        let first_line = self.start.line();
+        let first_col = self.start.column();
+        let (last_line, last_col) = {
+            if self.end.line() > first_line && self.end.column() <= 1 {
+                let line = self.end.line() - 1;
+                (line, context.line_length(line) + 1)
+            } else {
+                (self.end.line(), self.end.column())
+            }
+        };
+        let text = Some(self.text.as_str());
+        if last_line <= first_line {
+            marks.push(Mark {
+                line: first_line,
+                first_col,
+                num_cols: last_col.saturating_sub(first_col),
+                primary: self.primary,
+                text,
+            });
+        } else {
+            let line_end = context.line_length(first_line) + 1;
+            marks.push(Mark {
+                line: first_line,
+                first_col,
+                num_cols: line_end.saturating_sub(first_col),
+                primary: self.primary,
+                text: None,
+            });
+            marks.push(Mark {
+                line: last_line,
+                first_col: 1,
+                num_cols: last_col.saturating_sub(1),
+                primary: self.primary,
+                text,
+            });
+        }
+    }
+}
+
+//a Diagnostic
+//tp Diagnostic
+/// A diagnostic message, such as an error, with labelled spans of the
+/// source text, notes and help
+///
+/// A [Diagnostic] is built with a severity and message, and then
+/// (optionally) a primary span, any number of secondary labelled
+/// spans, notes and help text. It is rendered with
+/// [Diagnostic::fmt_with], using an [FmtContext] (such as a
+/// [crate::LexerOfString]) for the source text, in a form similar to
+/// compiler error messages:
+///
+/// ```text
+/// error: unexpected character '1'
+///     --> 2:5
+///     |
+///    2|  bob 1
+///     |  --- while parsing this
+///     |      ^ expected an identifier
+///     |
+///     = help: identifiers must be alphabetic
+/// ```
+///
+/// The primary span is underlined with `^` and secondary spans with
+/// `-` (unless a different [ContextStyle] is used); each underline
+/// is followed by its label text. Underlines are sorted by line and
+/// then column, each having its own row so that overlapping spans
+/// remain clear. If the [ContextStyle] enables colour then the
+/// severity, gutter, underlines and labels are coloured.
+#[derive(Debug, Clone)]
+pub struct Diagnostic<P> {
+    severity: Severity,
+    message: String,
+    labels: Vec<Label<P>>,
+    notes: Vec<String>,
+    help: Vec<String>,
+}
+
+//ip Diagnostic
+impl<P> Diagnostic<P>
+where
+    P: UserPosn,
+{
+    //fp new
+    /// Create a new [Diagnostic] with a severity and message
+    pub fn new<S: Into<String>>(severity: Severity, message: S) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+            labels: Vec::new(),
+            notes: Vec::new(),
+            help: Vec::new(),
+        }
+    }
+
+    //fp error
+    /// Create a new error [Diagnostic] with a message
+    pub fn error<S: Into<String>>(message: S) -> Self {
+        Self::new(Severity::Error, message)
+    }
+
+    //fp warning
+    /// Create a new warning [Diagnostic] with a message
+    pub fn warning<S: Into<String>>(message: S) -> Self {
+        Self::new(Severity::Warning, message)
+    }
+
+    //cp with_primary
+    /// Set the primary span of the diagnostic, with a label (which
+    /// may be empty)
+    ///
+    /// This replaces any previous primary span
+    pub fn with_primary<S: Into<String>>(mut self, start: P, end: P, label: S) -> Self {
+        self.labels.retain(|l| !l.primary);
+        self.labels.insert(
+            0,
+            Label {
+                start,
+                end,
+                primary: true,
+                text: label.into(),
+            },
+        );
+        self
+    }
+
+    //cp with_label
+    /// Add a secondary labelled span to the diagnostic
+    pub fn with_label<S: Into<String>>(mut self, start: P, end: P, label: S) -> Self {
+        self.labels.push(Label {
+            start,
+            end,
+            primary: false,
+            text: label.into(),
+        });
+        self
+    }
+
+    //cp with_note
+    /// Add a note to the diagnostic
+    pub fn with_note<S: Into<String>>(mut self, note: S) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    //cp with_help
+    /// Add help text to the diagnostic
+    pub fn with_help<S: Into<String>>(mut self, help: S) -> Self {
+        self.help.push(help.into());
+        self
+    }
+
+    //ap severity
+    /// Get the severity of the diagnostic
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    //ap message
+    /// Get the message of the diagnostic
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    //ap primary
+    /// Get the start and end of the primary span, if there is one
+    pub fn primary(&self) -> Option<(P, P)> {
+        self.labels
+            .iter()
+            .find(|l| l.primary)
+            .map(|l| (l.start, l.end))
+    }
+
+    //mp fmt_with
+    /// Format the diagnostic, using an [FmtContext] for the lines of
+    /// the source text that the spans refer to
+    pub fn fmt_with<C>(&self, f: &mut dyn std::fmt::Write, context: &C) -> std::fmt::Result
+    where
+        C: FmtContext<P> + ?Sized,
+    {
+        self.fmt_with_style(f, context, &ContextStyle::default())
+    }
+
+    //mp fmt_with_style
+    /// Format the diagnostic, using an [FmtContext] for the lines of
+    /// the source text that the spans refer to, in a given
+    /// [ContextStyle]
+    ///
+    /// The gutter width, markers and tab expansion of the style are
+    /// used; all the lines with underlines are shown, and the lines of
+    /// context of the style do not apply
+    pub fn fmt_with_style<C>(
+        &self,
+        f: &mut dyn std::fmt::Write,
+        context: &C,
+        style: &ContextStyle,
+    ) -> std::fmt::Result
+    where
+        C: FmtContext<P> + ?Sized,
+    {
+        let mut marks = Vec::new();
+        for l in &self.labels {
+            l.add_marks(context, &mut marks);
+        }
+        marks.sort_by_key(|m| (m.line, m.first_col, !m.primary));
+        let width = style.gutter_width(marks.last().map(|m| m.line).unwrap_or(0));
+
+        style.paint(f, self.severity.paint(), format_args!("{}", self.severity))?;
+        style.paint(f, Paint::Bold, format_args!(": {}", self.message))?;
+        writeln!(f)?;
+        if let Some((start, _)) = self.primary() {
+            style.paint(
+                f,
+                Paint::Gutter,
+                format_args!("{:width$}-->", "", width = width),
+            )?;
+            f.write_char(' ')?;
+            context.fmt_location(f, &start)?;
+            writeln!(f)?;
+        }
+
+        if !marks.is_empty() {
+            style.fmt_separator(f, width)?;
+            let mut last_line = None;
+            for m in &marks {
+                if last_line != Some(m.line) {
+                    if let Some(last_line) = last_line {
+                        if m.line == last_line + 2 {
+                            style.fmt_gutter(f, width, None)?;
+                            style.fmt_line(f, context, last_line + 1)?;
+                            writeln!(f)?;
+                        } else if m.line > last_line + 2 {
+                            style.fmt_ellipsis(f, width)?;
+                        }
+                    }
+                    style.fmt_gutter(f, width, Some(m.line))?;
+                    style.fmt_line(f, context, m.line)?;
+                    writeln!(f)?;
+                    last_line = Some(m.line);
+                }
+                let first_col = style.visual_column(context, m.line, m.first_col);
+                let end_col = style.visual_column(context, m.line, m.first_col + m.num_cols);
+                let (marker, paint) = if m.primary {
+                    (style.marker, self.severity.paint())
+                } else {
+                    (style.secondary_marker, Paint::Secondary)
+                };
+                style.fmt_marker(f, width, first_col, end_col - first_col, marker, paint)?;
+                if let Some(text) = m.text {
+                    if !text.is_empty() {
+                        style.paint(f, paint, format_args!(" {}", text))?;
+                    }
+                }
+                writeln!(f)?;
+            }
+            if !self.notes.is_empty() || !self.help.is_empty() {
+                style.fmt_separator(f, width)?;
+            }
+        }
+
+        for (kind, text) in self
+            .notes
+            .iter()
+            .map(|n| ("note", n))
+            .chain(self.help.iter().map(|h| ("help", h)))
+        {
+            style.paint(
+                f,
+                Paint::Gutter,
+                format_args!("{:width$}=", "", width = width),
+            )?;
+            f.write_char(' ')?;
+            style.paint(f, Paint::Bold, format_args!("{}:", kind))?;
+            writeln!(f, " {}", text)?;
+        }
+        Ok(())
+    }
+}
+
+//ip From<SimpleParseError> for Diagnostic
+impl<P> From<SimpleParseError<P>> for Diagnostic<P>
+where
+    P: UserPosn,
+{
+    fn from(e: SimpleParseError<P>) -> Self {
+        let end = e.pos.advance_cols(e.ch.len_utf8(), 1);
+        let message = match e.kind() {
+            SimpleParseErrorKind::FailedToParse => format!("unexpected character '{}'", e.ch),
+            SimpleParseErrorKind::Unterminated => format!("unterminated '{}'", e.ch),
+            SimpleParseErrorKind::BadEscape => format!("bad escape '\\{}'", e.ch),
+            SimpleParseErrorKind::BadIndent => "inconsistent indentation".to_string(),
+            SimpleParseErrorKind::ReadError => "failed to read the stream".to_string(),
+        };
+        Self::error(message).with_primary(e.pos, end, "")
+    }
+}
@@ -0,0 +1,97 @@
+//a Imports
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+//a Symbol
+//tp Symbol
+/// A small [Copy] handle for a string interned in an [Interner]
+///
+/// Symbols from the same [Interner] are equal if and only if their
+/// strings are equal, so they may be compared and hashed cheaply in
+/// place of the strings; the string is retrieved with
+/// [Interner::resolve].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u32);
+
+//ip Symbol
+impl Symbol {
+    //ap index
+    /// Get the index of the symbol within its [Interner]
+    pub fn index(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+//a Interner
+//ti InternerState
+/// The strings of an [Interner], and the symbols for them
+#[derive(Debug, Default)]
+struct InternerState {
+    symbols: HashMap<Rc<str>, Symbol>,
+    strings: Vec<Rc<str>>,
+}
+
+//tp Interner
+/// A table of interned strings, each with a [Symbol]
+///
+/// Interning uses interior mutability, so an [Interner] may be shared
+/// by reference - in particular with a lexer (using
+/// [crate::LexerOfStr::with_interner], or the same method of
+/// [crate::DfaLexer] or [crate::ModalLexer]), so that parse functions
+/// can intern the text of tokens through [crate::CharStream::interner]
+/// and the tokens can hold [Copy] symbols rather than strings.
+#[derive(Debug, Default)]
+pub struct Interner {
+    state: RefCell<InternerState>,
+}
+
+//ip Interner
+impl Interner {
+    //fp new
+    /// Create a new empty [Interner]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    //mp intern
+    /// Get the [Symbol] for a string, interning it if required
+    ///
+    /// Panics if more than `u32::MAX` strings would be interned
+    pub fn intern(&self, s: &str) -> Symbol {
+        if let Some(symbol) = self.get(s) {
+            return symbol;
+        }
+        let mut state = self.state.borrow_mut();
+        let index = u32::try_from(state.strings.len()).expect("Too many strings interned");
+        let symbol = Symbol(index);
+        let s: Rc<str> = s.into();
+        state.strings.push(s.clone());
+        state.symbols.insert(s, symbol);
+        symbol
+    }
+
+    //mp get
+    /// Get the [Symbol] for a string if it has been interned
+    pub fn get(&self, s: &str) -> Option<Symbol> {
+        self.state.borrow().symbols.get(s).copied()
+    }
+
+    //mp resolve
+    /// Get the string for a [Symbol], if it is from this [Interner]
+    pub fn resolve(&self, symbol: Symbol) -> Option<Rc<str>> {
+        self.state.borrow().strings.get(symbol.index()).cloned()
+    }
+
+    //ap len
+    /// Get the number of strings interned
+    pub fn len(&self) -> usize {
+        self.state.borrow().strings.len()
+    }
+
+    //ap is_empty
+    /// Return true if no strings have been interned
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
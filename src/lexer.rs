@@ -1,13 +1,24 @@
+mod indent;
+mod lexer_of_reader;
 mod lexer_of_str;
 mod lexer_of_string;
+mod modal_lexer;
 mod parser_iter;
+mod recovery;
 mod simple_parse_error;
 mod traits;
+mod trivia;
 
-pub use parser_iter::ParserIterator;
+pub use parser_iter::{ParserIterator, SpannedParserIterator};
 
+pub use indent::{IndentIterator, IndentToken};
+pub use lexer_of_reader::{CompactingIterator, LexerOfReader};
 pub use lexer_of_str::LexerOfStr;
-pub use lexer_of_string::LexerOfString;
-pub use traits::{BoxDynLexerParseFn, Lexer, LexerError, LexerParseFn, LexerParseResult};
+pub use lexer_of_string::{LexerOfString, TextEdit};
+pub use modal_lexer::ModalLexer;
+pub use recovery::LexerRecovery;
+pub use traits::{BoxDynLexerParseFn, BoxDynLexerRecoveryFn};
+pub use traits::{Lexer, LexerError, LexerParseFn, LexerParseResult};
+pub use trivia::{TriviaLexer, TriviaToken, TriviaTokens};
 
-pub use simple_parse_error::SimpleParseError;
+pub use simple_parse_error::{SimpleParseError, SimpleParseErrorKind};
@@ -0,0 +1,203 @@
+//a Imports
+use std::collections::VecDeque;
+use std::iter::FusedIterator;
+
+use crate::{CharStream, Lexer, LexerError, PosnInCharStream};
+use crate::{SpannedParserIterator, StreamCharSpan, UserPosn};
+
+//a IndentToken
+//tp IndentToken
+/// A token delivered by an [IndentIterator] - either a token of the
+/// [Lexer], or a synthetic token for the end of a line or a change in
+/// indentation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndentToken<T> {
+    /// The end of a (non-blank) line
+    Newline,
+    /// An increase in indentation, before the first token of a line
+    Indent,
+    /// A decrease in indentation, before the first token of a line;
+    /// one is delivered for each level of indentation closed
+    Dedent,
+    /// A token of the [Lexer]
+    Token(T),
+}
+
+//a IndentIterator
+//tp IndentIterator
+/// An iterator over the tokens of a [Lexer] for an
+/// indentation-sensitive language (such as Python), which adds
+/// [IndentToken::Newline], [IndentToken::Indent] and
+/// [IndentToken::Dedent] tokens to those of the lexer
+///
+/// Tokens for which the 'is_trivia' predicate returns true (such as
+/// whitespace and comments) are not delivered by the iterator, and
+/// lines that contain only such tokens (blank or comment-only lines)
+/// are ignored.
+///
+/// The indentation of a line is the column of its first token that
+/// is not trivia; tabs count as a single column, so a language that
+/// permits tabs should be consistent in its use of them. If the
+/// indentation is greater than that of the previous line then it is
+/// pushed on a stack of indentation and an Indent is delivered; if
+/// it is less, then a Dedent is delivered for each level popped from
+/// the stack, and the indentation must then match that of the level
+/// at the top of the stack - otherwise a 'bad_indent' error of the
+/// [LexerError] is returned at the token.
+///
+/// A Newline is delivered at the end of each line that has tokens
+/// (before any Indent or Dedent for the next line), and at the end of
+/// the stream a Newline is followed by a Dedent for each level of
+/// indentation that remains.
+///
+/// A token that spans lines (such as a multi-line string) is part of
+/// the line on which it starts.
+///
+/// Lines and indentation are only tracked if the state of the lexer
+/// tracks them; with a state that does not (such as usize, whose line
+/// and column are always 0) the whole stream is a single line with no
+/// indentation.
+///
+/// This is created with [Lexer::iter_indented]
+pub struct IndentIterator<'a, L>
+where
+    L: Lexer,
+{
+    lexer: &'a L,
+    tokens: SpannedParserIterator<'a, L>,
+    is_trivia: &'a dyn Fn(&L::Token) -> bool,
+    indents: Vec<usize>,
+    pending: VecDeque<IndentToken<L::Token>>,
+    last_line: Option<usize>,
+    finished: bool,
+}
+
+//ip IndentIterator
+impl<'a, L> IndentIterator<'a, L>
+where
+    L: Lexer + CharStream<<L as Lexer>::State>,
+    L::State: PosnInCharStream,
+{
+    //fp new
+    /// Create a new [IndentIterator] from the tokens of a lexer, with a
+    /// predicate identifying trivia tokens
+    pub fn new(
+        lexer: &'a L,
+        tokens: SpannedParserIterator<'a, L>,
+        is_trivia: &'a dyn Fn(&L::Token) -> bool,
+    ) -> Self {
+        Self {
+            lexer,
+            tokens,
+            is_trivia,
+            indents: vec![0],
+            pending: VecDeque::new(),
+            last_line: None,
+            finished: false,
+        }
+    }
+
+    //ap depth
+    /// Get the number of levels of indentation currently open
+    pub fn depth(&self) -> usize {
+        self.indents.len() - 1
+    }
+
+    //mi start_line
+    /// Add the pending tokens for the start of a line whose first
+    /// token that is not trivia is at 'start'
+    fn start_line(&mut self, start: &L::State) -> Result<(), L::Error> {
+        if self.last_line.is_some() {
+            self.pending.push_back(IndentToken::Newline);
+        }
+        let indent = start.column().saturating_sub(1);
+        let mut top = *self.indents.last().unwrap();
+        if indent > top {
+            self.indents.push(indent);
+            self.pending.push_back(IndentToken::Indent);
+            return Ok(());
+        }
+        while indent < top {
+            self.indents.pop();
+            self.pending.push_back(IndentToken::Dedent);
+            top = *self.indents.last().unwrap();
+        }
+        if indent != top {
+            let ch = self.lexer.peek_at(start).unwrap_or(' ');
+            return Err(L::Error::bad_indent(*start, ch));
+        }
+        Ok(())
+    }
+
+    //mi end_stream
+    /// Add the pending tokens for the end of the stream
+    fn end_stream(&mut self) {
+        if self.last_line.is_some() {
+            self.pending.push_back(IndentToken::Newline);
+        }
+        while self.indents.len() > 1 {
+            self.indents.pop();
+            self.pending.push_back(IndentToken::Dedent);
+        }
+    }
+
+    //mi add_token
+    /// Add the pending tokens for a token of the lexer that is not
+    /// trivia
+    fn add_token(
+        &mut self,
+        span: StreamCharSpan<L::State>,
+        token: L::Token,
+    ) -> Result<(), L::Error> {
+        let start = span.start();
+        let new_line = match self.last_line {
+            None => true,
+            Some(line) => start.line() > line,
+        };
+        if new_line {
+            self.start_line(start)?;
+        }
+        self.last_line = Some(span.end().line());
+        self.pending.push_back(IndentToken::Token(token));
+        Ok(())
+    }
+}
+
+//ip Iterator for IndentIterator
+impl<'a, L> Iterator for IndentIterator<'a, L>
+where
+    L: Lexer + CharStream<<L as Lexer>::State>,
+    L::State: PosnInCharStream,
+{
+    type Item = Result<IndentToken<L::Token>, L::Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() && !self.finished {
+            match self.tokens.next() {
+                None => {
+                    self.end_stream();
+                    self.finished = true;
+                }
+                Some(Err(e)) => {
+                    return Some(Err(e));
+                }
+                Some(Ok((_, token))) if (self.is_trivia)(&token) => (),
+                Some(Ok((span, token))) => {
+                    if let Err(e) = self.add_token(span, token) {
+                        self.pending.clear();
+                        self.finished = true;
+                        return Some(Err(e));
+                    }
+                }
+            }
+        }
+        self.pending.pop_front().map(Ok)
+    }
+}
+
+//ip FusedIterator for IndentIterator
+impl<'a, L> FusedIterator for IndentIterator<'a, L>
+where
+    L: Lexer + CharStream<<L as Lexer>::State>,
+    L::State: PosnInCharStream,
+{
+}
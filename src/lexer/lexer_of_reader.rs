@@ -0,0 +1,612 @@
+//a Imports
+use std::cell::{Cell, RefCell};
+use std::io::BufRead;
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+
+use crate::BoxDynLexerParseFn;
+use crate::{CharStream, Lexer, LexerError, LexerParseResult};
+use crate::{ParserIterator, PosnInCharStream, StreamCharSpan};
+
+//a Chunk, ReaderData
+//ti Chunk
+/// A chunk of the data of the stream, starting at a byte offset
+/// within the stream
+///
+/// The data of a chunk is a heap allocation that is never modified
+/// once the chunk is created, and which does not move when the chunk
+/// itself is moved (such as when a vector of chunks is reallocated);
+/// references to the data are handed out for the lifetime of a
+/// borrow of the [LexerOfReader], hence a chunk is only dropped when
+/// the [LexerOfReader] is mutably borrowed.
+#[derive(Debug)]
+struct Chunk {
+    start: usize,
+    data: Box<[u8]>,
+}
+
+//ii Chunk
+impl Chunk {
+    //mi end
+    /// Get the byte offset of the end of the chunk
+    fn end(&self) -> usize {
+        self.start + self.data.len()
+    }
+
+    //mi contains
+    /// Return true if the chunk contains the byte range
+    fn contains(&self, start: usize, end: usize) -> bool {
+        self.start <= start && end <= self.end()
+    }
+}
+
+//ti ReaderData
+/// The data that has been read from the stream (and not yet dropped)
+#[derive(Debug, Default)]
+struct ReaderData {
+    /// Chunks of data in the order they were read - so they are
+    /// ordered by start, and are contiguous
+    chunks: Vec<Chunk>,
+    /// Chunks created to provide contiguous data for byte ranges that
+    /// span more than one of 'chunks'
+    merged: Vec<Chunk>,
+    /// Byte offset of the start of the data retained
+    start: usize,
+    /// Byte offset of the end of the valid UTF8 data read so far
+    end: usize,
+    /// Bytes read from the stream that form an incomplete UTF8
+    /// character
+    pending: Vec<u8>,
+    /// Set if the end of the stream has been reached, or an error
+    /// has occurred
+    eof: bool,
+    /// The error (if any) that occurred reading the stream
+    error: Option<std::io::Error>,
+}
+
+//ii ReaderData
+impl ReaderData {
+    //mi read_more
+    /// Read another chunk of data from the stream
+    fn read_more<R: BufRead>(&mut self, reader: &mut R) {
+        let data = match reader.fill_buf() {
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {
+                return;
+            }
+            Err(e) => {
+                self.eof = true;
+                self.error = Some(e);
+                return;
+            }
+            Ok(data) => data,
+        };
+        if data.is_empty() {
+            self.eof = true;
+            if !self.pending.is_empty() {
+                self.error = Some(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    "stream ended within a UTF8 character",
+                ));
+            }
+            return;
+        }
+        let mut bytes = std::mem::take(&mut self.pending);
+        bytes.extend_from_slice(data);
+        let n = data.len();
+        reader.consume(n);
+
+        let valid = match std::str::from_utf8(&bytes) {
+            Ok(_) => bytes.len(),
+            Err(e) => {
+                if e.error_len().is_some() {
+                    self.eof = true;
+                    self.error = Some(std::io::Error::new(std::io::ErrorKind::InvalidData, e));
+                }
+                e.valid_up_to()
+            }
+        };
+        if !self.eof {
+            self.pending = bytes.split_off(valid);
+        } else {
+            bytes.truncate(valid);
+        }
+        if !bytes.is_empty() {
+            let start = self.end;
+            self.end += bytes.len();
+            self.chunks.push(Chunk {
+                start,
+                data: bytes.into_boxed_slice(),
+            });
+        }
+    }
+
+    //mi find
+    /// Find a chunk that contains the byte range, if there is one
+    fn find(&self, start: usize, end: usize) -> Option<&Chunk> {
+        let n = self.chunks.partition_point(|c| c.end() <= start);
+        if let Some(c) = self.chunks.get(n) {
+            if c.contains(start, end) {
+                return Some(c);
+            }
+        }
+        self.merged.iter().rev().find(|c| c.contains(start, end))
+    }
+
+    //mi merge
+    /// Create a merged chunk that contains the byte range from start
+    /// to the end of the data read so far
+    ///
+    /// The data must already have been read, and not dropped, for the
+    /// range
+    fn merge(&mut self, start: usize) -> &Chunk {
+        let mut data = Vec::with_capacity(self.end - start);
+        let n = self.chunks.partition_point(|c| c.end() <= start);
+        for c in &self.chunks[n..] {
+            let from = start.max(c.start) - c.start;
+            data.extend_from_slice(&c.data[from..]);
+        }
+        self.merged.push(Chunk {
+            start,
+            data: data.into_boxed_slice(),
+        });
+        self.merged.last().unwrap()
+    }
+
+    //mi drop_before
+    /// Drop all chunks that only contain data before a byte offset
+    fn drop_before(&mut self, ofs: usize) {
+        let n = self.chunks.partition_point(|c| c.end() <= ofs);
+        self.chunks.drain(0..n);
+        self.merged.retain(|c| c.start >= ofs);
+        self.start = self.chunks.first().map_or(self.end, |c| c.start);
+    }
+}
+
+//a LexerOfReader
+//tp LexerOfReader
+/// A [Lexer] of a stream that is read from a [BufRead], using an
+/// arbitrary stream position type, lexer token, and lexer error.
+///
+/// This provides implementations of [Lexer] and [CharStream], in the
+/// same manner as [crate::LexerOfStr]; the UTF8 data of the stream
+/// is read from the [BufRead] in chunks, as it is required by the
+/// parser functions. UTF8 characters that are split between chunks
+/// read from the stream are handled correctly.
+///
+/// The data read from the stream is retained until the stream has
+/// been *committed* past it - using [CharStream::commit_consumed] -
+/// *and* the [LexerOfReader] is compacted with
+/// [LexerOfReader::compact]. Compacting requires a mutable borrow of
+/// the [LexerOfReader], as the parser functions may have borrowed
+/// text from it.
+///
+/// The simplest way to parse a large stream is to use
+/// [LexerOfReader::iter_compacting], or [LexerOfReader::next_token]
+/// which parses the next token, commits the stream to the end of that
+/// token and compacts the data; these retain only the data of the
+/// stream beyond the last token parsed. The [Lexer] iterators (such
+/// as 'iter') only borrow the [LexerOfReader], and so they retain all
+/// of the data of the stream that is read; they are suitable only for
+/// streams that fit in memory.
+///
+/// If an error occurs reading the stream (including if the stream is
+/// not valid UTF8) then the stream is truncated at that point, and
+/// parsing at the end of the truncated stream returns a 'read_error'
+/// of the [LexerError] rather than the end of the stream; the
+/// underlying error can be retrieved with [LexerOfReader::take_error].
+#[derive(Debug)]
+pub struct LexerOfReader<R, P, T, E>
+where
+    R: BufRead,
+    P: PosnInCharStream,
+{
+    reader: RefCell<R>,
+    data: RefCell<ReaderData>,
+    committed: Cell<usize>,
+    _phantom_posn: PhantomData<P>,
+    _phantom_token: PhantomData<T>,
+    _phantom_error: PhantomData<E>,
+}
+
+//ip LexerOfReader
+impl<R, P, T, E> LexerOfReader<R, P, T, E>
+where
+    R: BufRead,
+    P: PosnInCharStream,
+    T: Sized + std::fmt::Debug,
+    E: LexerError<P>,
+{
+    //fp new
+    /// Create a new [LexerOfReader] that reads from a [BufRead]
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: RefCell::new(reader),
+            data: RefCell::new(ReaderData::default()),
+            committed: Cell::new(0),
+            _phantom_posn: PhantomData,
+            _phantom_token: PhantomData,
+            _phantom_error: PhantomData,
+        }
+    }
+
+    //mp next_token
+    /// Parse the next token found at the state of the stream, and
+    /// commit the stream to the end of that token
+    ///
+    /// The data of the stream before the end of the token is then
+    /// dropped, and it may not be accessed again.
+    pub fn next_token(
+        &mut self,
+        state: P,
+        parsers: &[BoxDynLexerParseFn<'_, Self>],
+    ) -> LexerParseResult<P, T, E>
+    where
+        R: std::fmt::Debug,
+    {
+        let result = self.parse(state, parsers)?;
+        if let Some((end, _)) = &result {
+            self.commit_consumed(end);
+            self.compact();
+        }
+        Ok(result)
+    }
+
+    //mp iter_compacting
+    /// Create an iterator over the tokens of the stream that, like
+    /// [LexerOfReader::next_token], commits and compacts the stream
+    /// after each token, so that only the data of the stream beyond
+    /// the last token parsed is retained
+    ///
+    /// After an error the iterator stops, returning None thereafter
+    pub fn iter_compacting<'iter>(
+        &'iter mut self,
+        parsers: &'iter [BoxDynLexerParseFn<'iter, Self>],
+    ) -> CompactingIterator<'iter, R, P, T, E>
+    where
+        R: std::fmt::Debug,
+    {
+        let state = self.start();
+        CompactingIterator {
+            lexer: self,
+            parsers,
+            state,
+            finished: false,
+        }
+    }
+
+    //mp compact
+    /// Drop all of the data of the stream before the committed
+    /// position
+    ///
+    /// The data may not be accessed again; the [CharStream] methods
+    /// treat it as beyond the end of the stream
+    pub fn compact(&mut self) {
+        let committed = self.committed.get();
+        self.data.get_mut().drop_before(committed);
+    }
+
+    //mp retained_bytes
+    /// Get the number of bytes of data of the stream that are
+    /// retained by the [LexerOfReader]
+    pub fn retained_bytes(&self) -> usize {
+        let data = self.data.borrow();
+        data.chunks.iter().map(|c| c.data.len()).sum::<usize>()
+            + data.merged.iter().map(|c| c.data.len()).sum::<usize>()
+    }
+
+    //mp take_error
+    /// Take the error (if any) that occurred when reading the stream
+    ///
+    /// After this, parsing at the end of the truncated stream returns
+    /// the end of the stream rather than a 'read_error'
+    pub fn take_error(&mut self) -> Option<std::io::Error> {
+        self.data.get_mut().error.take()
+    }
+
+    //mp take_reader
+    /// Drop the [LexerOfReader] and return the underlying [BufRead]
+    pub fn take_reader(self) -> R {
+        self.reader.into_inner()
+    }
+
+    //mi ensure
+    /// Ensure that the data of the stream has been read up to the
+    /// byte offset, returning false if the stream ends before it
+    fn ensure(&self, end: usize) -> bool {
+        let mut data = self.data.borrow_mut();
+        while data.end < end {
+            if data.eof {
+                return false;
+            }
+            data.read_more(&mut *self.reader.borrow_mut());
+        }
+        true
+    }
+
+    //mi bytes
+    /// Borrow a contiguous range of bytes of the stream, or None if
+    /// the stream ends before the end of the range, or if the start of
+    /// the range has been dropped by [LexerOfReader::compact]
+    fn bytes(&self, start: usize, end: usize) -> Option<&[u8]> {
+        if !self.ensure(end) {
+            return None;
+        }
+        let mut data = self.data.borrow_mut();
+        if start < data.start {
+            return None;
+        }
+        let chunk = {
+            if data.find(start, end).is_none() {
+                data.merge(start);
+            }
+            data.find(start, end).unwrap()
+        };
+        let bytes = &chunk.data[(start - chunk.start)..(end - chunk.start)];
+        // SAFETY: 'bytes' is within the boxed data of a chunk, which
+        // is a heap allocation that is never modified. Pushing chunks
+        // to 'chunks' or 'merged' may reallocate those vectors, but
+        // that moves only the Chunk (the pointer to the data), not
+        // the data itself. Chunks are only removed by 'drop_before',
+        // which is only invoked from 'compact' with a mutable borrow
+        // of self, and so cannot occur while the shared borrow of
+        // self that the slice is given the lifetime of is live; the
+        // same applies to dropping self. Hence the data is valid for
+        // the lifetime of the borrow of self, even though the RefCell
+        // borrow ends here.
+        Some(unsafe { std::slice::from_raw_parts(bytes.as_ptr(), bytes.len()) })
+    }
+
+    //mi peek_at_offset
+    /// Get the utf8 chararacter at the byte offset, or None at the
+    /// end of the stream
+    fn peek_at_offset(&self, byte_ofs: usize) -> Option<char> {
+        let bytes = self.bytes(byte_ofs, byte_ofs + 1)?;
+        let n = match bytes[0] {
+            b if b < 0x80 => 1,
+            b if b < 0xe0 => 2,
+            b if b < 0xf0 => 3,
+            _ => 4,
+        };
+        let bytes = self.bytes(byte_ofs, byte_ofs + n)?;
+        // SAFETY: byte_ofs is a UTF8 character boundary, the data of
+        // the stream is valid UTF8, and 'n' is the length of the
+        // character given by its first byte
+        let text = unsafe { std::str::from_utf8_unchecked(bytes) };
+        text.chars().next()
+    }
+}
+
+//a Impl Lexer, CharStream
+//ip Lexer for LexerOfReader
+impl<R, P, T, E> Lexer for LexerOfReader<R, P, T, E>
+where
+    R: BufRead + std::fmt::Debug,
+    P: PosnInCharStream,
+    T: Sized + std::fmt::Debug,
+    E: LexerError<P>,
+{
+    type Token = T;
+    type Error = E;
+    type State = P;
+
+    //mp parse
+    fn parse<'iter>(
+        &'iter self,
+        state: Self::State,
+        parsers: &[BoxDynLexerParseFn<'iter, Self>],
+    ) -> LexerParseResult<Self::State, Self::Token, Self::Error> {
+        if let Some(ch) = self.peek_at(&state) {
+            for p in parsers {
+                let result = p(self, state, ch)?;
+                if result.is_some() {
+                    return Ok(result);
+                }
+            }
+            return Err(E::failed_to_parse(state, ch));
+        }
+        if let Some(e) = &self.data.borrow().error {
+            return Err(E::read_error(state, e));
+        }
+        Ok(None)
+    }
+
+    //mp iter
+    fn iter<'iter>(
+        &'iter self,
+        parsers: &'iter [BoxDynLexerParseFn<'iter, Self>],
+    ) -> Box<dyn Iterator<Item = Result<T, E>> + 'iter> {
+        Box::new(ParserIterator::new(self, self.start(), parsers))
+    }
+}
+
+//ip CharStream for LexerOfReader
+impl<R, P, T, E> CharStream<P> for LexerOfReader<R, P, T, E>
+where
+    R: BufRead,
+    P: PosnInCharStream,
+    T: Sized + std::fmt::Debug,
+    E: LexerError<P>,
+{
+    //mp range_as_bytes
+    /// Borrow some bytes of the stream from an offset
+    ///
+    /// Panics if the bytes are out of range, or have been dropped by
+    /// [LexerOfReader::compact]
+    fn range_as_bytes(&self, ofs: usize, n: usize) -> &[u8] {
+        self.bytes(ofs, ofs + n)
+            .expect("Range of bytes must be within the stream")
+    }
+
+    //mp get_text_span
+    /// Get the text of a [StreamCharSpan] provided by a parser
+    ///
+    /// # Safety
+    ///
+    /// The [StreamCharSpan] must have been provided by a parser and
+    /// so the byte offsets are indeed utf8 character boundaries
+    fn get_text_span(&self, span: &StreamCharSpan<P>) -> &str {
+        self.get_text(*span.start(), *span.end())
+    }
+
+    //mp get_text
+    /// Get the text between two [crate::StreamCharPos] provided by a parser
+    ///
+    /// # Safety
+    ///
+    /// The [crate::StreamCharPos] must have been provided by a parser and
+    /// so the byte offsets are indeed utf8 character boundaries
+    fn get_text(&self, start: P, end: P) -> &str {
+        let bytes = self.range_as_bytes(start.byte_ofs(), end.byte_ofs() - start.byte_ofs());
+        // SAFETY: the positions were provided by a parser, and so are
+        // UTF8 character boundaries within the stream, whose data is
+        // valid UTF8
+        unsafe { std::str::from_utf8_unchecked(bytes) }
+    }
+
+    //mp peek_at
+    /// Get the utf8 chararacter at the state, reading more of the
+    /// stream if required, or None at the end of the stream
+    fn peek_at(&self, state: &P) -> Option<char> {
+        self.peek_at_offset(state.byte_ofs())
+    }
+
+    //mp matches_bytes
+    /// Match the text at the offset with a str
+    fn matches_bytes(&self, state: &P, s: &[u8]) -> bool {
+        let byte_ofs = state.byte_ofs();
+        match self.bytes(byte_ofs, byte_ofs + s.len()) {
+            Some(bytes) => s == bytes,
+            None => false,
+        }
+    }
+
+    //mp matches_str
+    /// Match the text at the offset with a str
+    fn matches_str(&self, pos: &P, pat: &str) -> bool {
+        self.matches_bytes(pos, pat.as_bytes())
+    }
+
+    //cp consumed
+    fn consumed(&self, mut state: P, mut n: usize) -> P {
+        while n > 0 {
+            match self.peek_at(&state) {
+                Some(ch) => {
+                    state = self.consumed_char(state, ch);
+                }
+                None => {
+                    break;
+                }
+            }
+            n -= 1;
+        }
+        state
+    }
+
+    //mp do_while
+    fn do_while<F: Fn(usize, char) -> bool>(
+        &self,
+        mut state: P,
+        ch: char,
+        f: &F,
+    ) -> (P, Option<(P, usize)>) {
+        if !f(0, ch) {
+            return (state, None);
+        }
+        let start = state;
+        let mut n = 1;
+        state = self.consumed_char(state, ch);
+        while let Some(ch) = self.peek_at(&state) {
+            if !f(n, ch) {
+                break;
+            }
+            n += 1;
+            state = self.consumed_char(state, ch);
+        }
+        (state, Some((start, n)))
+    }
+
+    //mp commit_consumed
+    /// Commit the stream up to a state; data before this state will
+    /// be dropped when the [LexerOfReader] is next compacted
+    fn commit_consumed(&self, up_to: &P) {
+        let ofs = up_to.byte_ofs();
+        if ofs > self.committed.get() {
+            self.committed.set(ofs);
+        }
+    }
+}
+
+//a CompactingIterator
+//tp CompactingIterator
+/// An iterator over the tokens of a [LexerOfReader] that commits and
+/// compacts the stream after each token, so that only the data of the
+/// stream beyond the last token parsed is retained
+///
+/// This is created with [LexerOfReader::iter_compacting]
+pub struct CompactingIterator<'a, R, P, T, E>
+where
+    R: BufRead + std::fmt::Debug,
+    P: PosnInCharStream,
+    T: Sized + std::fmt::Debug,
+    E: LexerError<P>,
+{
+    lexer: &'a mut LexerOfReader<R, P, T, E>,
+    parsers: &'a [BoxDynLexerParseFn<'a, LexerOfReader<R, P, T, E>>],
+    state: P,
+    finished: bool,
+}
+
+//ip CompactingIterator
+impl<'a, R, P, T, E> CompactingIterator<'a, R, P, T, E>
+where
+    R: BufRead + std::fmt::Debug,
+    P: PosnInCharStream,
+    T: Sized + std::fmt::Debug,
+    E: LexerError<P>,
+{
+    //ap state
+    /// Get the state of the stream after the last token parsed
+    pub fn state(&self) -> P {
+        self.state
+    }
+}
+
+//ip Iterator for CompactingIterator
+impl<'a, R, P, T, E> Iterator for CompactingIterator<'a, R, P, T, E>
+where
+    R: BufRead + std::fmt::Debug,
+    P: PosnInCharStream,
+    T: Sized + std::fmt::Debug,
+    E: LexerError<P>,
+{
+    type Item = Result<T, E>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.lexer.next_token(self.state, self.parsers) {
+            Ok(Some((end, token))) => {
+                self.state = end;
+                Some(Ok(token))
+            }
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+//ip FusedIterator for CompactingIterator
+impl<'a, R, P, T, E> FusedIterator for CompactingIterator<'a, R, P, T, E>
+where
+    R: BufRead + std::fmt::Debug,
+    P: PosnInCharStream,
+    T: Sized + std::fmt::Debug,
+    E: LexerError<P>,
+{
+}
@@ -1,7 +1,7 @@
 //a Imports
 use std::marker::PhantomData;
 
-use crate::BoxDynLexerParseFn;
+use crate::{BoxDynLexerParseFn, Interner};
 use crate::{CharStream, Lexer, LexerError, LexerParseResult};
 use crate::{ParserIterator, PosnInCharStream, StreamCharSpan};
 
@@ -46,6 +46,54 @@ use crate::{ParserIterator, PosnInCharStream, StreamCharSpan};
 ///
 /// This provides methods to match strings, get
 ///
+/// # Tokens borrowing the text
+///
+/// The text returned by [CharStream::get_text] is borrowed only for
+/// the duration of a parse function call; the token type may instead
+/// borrow the text itself (such as `Ident(&'a str)`), using
+/// [LexerOfStr::get_str] which returns text with the lifetime of the
+/// [str]. The parse functions must be for the specific [LexerOfStr]
+/// type (that names the lifetime) rather than a generic [CharStream]:
+///
+/// ```
+/// use lexer_rs::{BoxDynLexerParseFn, CharStream, Lexer, LexerOfStr};
+/// use lexer_rs::{LexerParseResult, SimpleParseError, StreamCharPos};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Token<'a> {
+///     Ident(&'a str),
+///     Space,
+/// }
+/// type Pos = StreamCharPos<()>;
+/// type Error = SimpleParseError<Pos>;
+/// type TextStream<'a> = LexerOfStr<'a, Pos, Token<'a>, Error>;
+///
+/// fn parse<'a>(lexer: &TextStream<'a>, state: Pos, ch: char)
+///   -> LexerParseResult<Pos, Token<'a>, Error> {
+///     let (end, word) = lexer.do_while(state, ch, &|_, c| c.is_alphabetic());
+///     if let Some((start, _)) = word {
+///         return Ok(Some((end, Token::Ident(lexer.get_str(start, end)))));
+///     }
+///     let (end, space) = lexer.do_while(state, ch, &|_, c| c == ' ');
+///     Ok(space.map(|_| (end, Token::Space)))
+/// }
+///
+/// let text = String::from("borrowed words");
+/// let lexer = TextStream::new(&text);
+/// let parsers = [Box::new(parse) as BoxDynLexerParseFn<TextStream>];
+/// let words: Vec<&str> = lexer
+///     .iter(&parsers)
+///     .filter_map(|t| match t.unwrap() {
+///         Token::Ident(s) => Some(s),
+///         _ => None,
+///     })
+///     .collect();
+/// drop(parsers);
+/// drop(lexer);
+/// // The tokens borrow the text, not the lexer or parse functions
+/// assert_eq!(words, ["borrowed", "words"]);
+/// ```
+///
 // Cannot derive either Copy or Clone without that putting the same bound on T and E
 #[derive(Debug)]
 pub struct LexerOfStr<'a, P, T, E>
@@ -54,6 +102,8 @@ where
 {
     text: &'a str,
     end: usize,
+    start: P,
+    interner: Option<&'a Interner>,
     _phantom_posn: PhantomData<&'a P>,
     _phantom_token: PhantomData<&'a T>,
     _phantom_error: PhantomData<&'a E>,
@@ -76,22 +126,44 @@ where
 impl<'a, P, T, E> LexerOfStr<'a, P, T, E>
 where
     P: PosnInCharStream,
-    T: Sized + std::fmt::Debug + Copy,
+    T: Sized + std::fmt::Debug,
     E: LexerError<P>,
 {
     //fp new
     /// Create a new [LexerOfStr] by borrowing a [str]
     pub fn new(text: &'a str) -> Self {
-        let end = text.as_bytes().len();
+        let end = text.len();
         Self {
             text,
             end,
+            start: P::default(),
+            interner: None,
             _phantom_posn: PhantomData,
             _phantom_token: PhantomData,
             _phantom_error: PhantomData,
         }
     }
 
+    //cp with_interner
+    /// Set the [Interner] of the lexer, which parse functions may use
+    /// through [CharStream::interner] to intern the text of tokens
+    pub fn with_interner(mut self, interner: &'a Interner) -> Self {
+        self.interner = Some(interner);
+        self
+    }
+
+    //cp with_start
+    /// Set the state at the start of the text, from which the
+    /// iterators of the [Lexer] begin
+    ///
+    /// This permits the start state to carry more than a default
+    /// position, such as the [crate::FileId] of a [crate::FilePosn];
+    /// its byte offset must be zero
+    pub fn with_start(mut self, start: P) -> Self {
+        self.start = start;
+        self
+    }
+
     //mp peek_at_offset
     /// Get the utf8 chararacter at the byte offset, or None at the end of a string
     unsafe fn peek_at_offset(&self, byte_ofs: usize) -> Option<char> {
@@ -105,12 +177,42 @@ where
 
     //mp remaining_text
     /// Get the remaining text from a position
-    fn remaining_text(&self, p: &P) -> &str {
-        // # Safety
-        //
-        // Safe if p is a valid Posn as then it must be a utf8
-        // character boundary
-        unsafe { self.text.get_unchecked(p.byte_ofs()..self.end) }
+    ///
+    /// This permits matching of the text by other means than the
+    /// [CharStream] methods, such as with a regular expression
+    ///
+    /// Panics if the position is not at a character boundary within
+    /// the text (such as a position from a different text)
+    pub fn remaining_text(&self, p: &P) -> &'a str {
+        self.text
+            .get(p.byte_ofs()..self.end)
+            .expect("Position must be at a character boundary of the text")
+    }
+
+    //mp get_str
+    /// Get the text between two positions provided by a parser,
+    /// borrowed for the lifetime of the [str] rather than that of the
+    /// [LexerOfStr]
+    ///
+    /// Unlike [CharStream::get_text] this permits a token to borrow
+    /// its text from the source, as the text outlives the lexer and
+    /// the parse function call
+    ///
+    /// Panics if the positions are not at character boundaries within
+    /// the text (such as positions from a different text), or if
+    /// 'end' precedes 'start'
+    pub fn get_str(&self, start: P, end: P) -> &'a str {
+        self.text
+            .get(start.byte_ofs()..end.byte_ofs())
+            .expect("Positions must be at character boundaries of the text")
+    }
+
+    //mp get_str_span
+    /// Get the text of a [StreamCharSpan] provided by a parser,
+    /// borrowed for the lifetime of the [str]; see
+    /// [LexerOfStr::get_str]
+    pub fn get_str_span(&self, span: &StreamCharSpan<P>) -> &'a str {
+        self.get_str(*span.start(), *span.end())
     }
 }
 
@@ -119,7 +221,7 @@ where
 impl<'a, P, T, E> Lexer for LexerOfStr<'a, P, T, E>
 where
     P: PosnInCharStream,
-    T: Sized + std::fmt::Debug + Copy,
+    T: Sized + std::fmt::Debug,
     E: LexerError<P>,
 {
     type Token = T;
@@ -144,13 +246,17 @@ where
         Ok(None)
     }
 
+    //mp start
+    fn start(&self) -> P {
+        self.start
+    }
+
     //mp iter
     fn iter<'iter>(
         &'iter self,
         parsers: &'iter [BoxDynLexerParseFn<'iter, Self>],
     ) -> Box<dyn Iterator<Item = Result<T, E>> + 'iter> {
-        let state = Default::default();
-        Box::new(ParserIterator::new(self, state, parsers))
+        Box::new(ParserIterator::new(self, self.start, parsers))
     }
 }
 
@@ -158,7 +264,7 @@ where
 impl<'a, P, T, E> CharStream<P> for LexerOfStr<'a, P, T, E>
 where
     P: PosnInCharStream,
-    T: Sized + std::fmt::Debug + Copy,
+    T: Sized + std::fmt::Debug,
     E: LexerError<P>,
 {
     //mp range_as_bytes
@@ -203,6 +309,12 @@ where
         unsafe { self.peek_at_offset(state.byte_ofs()) }
     }
 
+    //ap interner
+    /// Get the [Interner] of the lexer, if it has one
+    fn interner(&self) -> Option<&Interner> {
+        self.interner
+    }
+
     //mp matches_bytes
     /// Match the text at the offset with a str
     fn matches_bytes(&self, state: &P, s: &[u8]) -> bool {
@@ -254,21 +366,35 @@ where
             return (state, None);
         }
         let start = state;
-        let mut n = 1;
-        let mut ofs = state.byte_ofs() + ch.len_utf8();
+        let mut n = 0;
+        let mut ofs = state.byte_ofs();
+        let mut line_chars = 0;
+        let mut ch = ch;
         // # Safety
         //
         // 'ofs' is maintained as a utf8 character point boundary
-        // within or at the end of the 'str' borrowed by [Self]
-        while let Some(ch) = unsafe { self.peek_at_offset(ofs) } {
-            if !f(n, ch) {
-                break;
-            }
+        // within or at the end of the 'str' borrowed by [Self], and
+        // 'state' is moved on to the start of each line as newlines
+        // are found
+        loop {
             n += 1;
             ofs += ch.len_utf8();
+            if ch == '\n' {
+                state =
+                    unsafe { self.consumed_chars(state, ofs - 1 - state.byte_ofs(), line_chars) };
+                state = unsafe { self.consumed_newline(state, 1) };
+                line_chars = 0;
+            } else {
+                line_chars += 1;
+            }
+            match unsafe { self.peek_at_offset(ofs) } {
+                Some(next_ch) if f(n, next_ch) => {
+                    ch = next_ch;
+                }
+                _ => break,
+            }
         }
-        // Does not work if newlines are involved
-        state = unsafe { self.consumed_chars(state, ofs - start.byte_ofs(), n) };
+        state = unsafe { self.consumed_chars(state, ofs - state.byte_ofs(), line_chars) };
         (state, Some((start, n)))
     }
 }
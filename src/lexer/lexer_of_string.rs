@@ -1,8 +1,46 @@
 //a Imports
 use std::marker::PhantomData;
+use std::ops::Range;
 
-use crate::{FmtContext, PosnInCharStream};
-use crate::{LexerError, LexerOfStr};
+use crate::{BoxDynLexerParseFn, CharStream, FmtContext, PosnInCharStream};
+use crate::{Lexer, LexerError, LexerOfStr, StreamCharSpan};
+
+//a TextEdit
+//tp TextEdit
+/// An edit applied to the text of a [LexerOfString] by
+/// [LexerOfString::edit], to be supplied to [LexerOfString::relex]
+///
+/// This records the byte range of the text that was replaced, and the
+/// end of the replacement in the new text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextEdit {
+    start: usize,
+    old_end: usize,
+    new_end: usize,
+}
+
+//ip TextEdit
+impl TextEdit {
+    //ap start
+    /// Get the byte offset of the start of the edit
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    //ap old_end
+    /// Get the byte offset of the end of the text replaced, in the old
+    /// text
+    pub fn old_end(&self) -> usize {
+        self.old_end
+    }
+
+    //ap new_end
+    /// Get the byte offset of the end of the replacement, in the new
+    /// text
+    pub fn new_end(&self) -> usize {
+        self.new_end
+    }
+}
 
 //a LexerOfString
 //tp LexerOfString
@@ -30,6 +68,12 @@ use crate::{LexerError, LexerOfStr};
 /// [LexerOfString] but retaining the text for future parse or
 /// compilation stages
 ///
+/// For an editor, the text may instead be edited in place with
+/// [LexerOfString::edit], which updates just the lines affected; the
+/// tokens of the text may then be updated with
+/// [LexerOfString::relex], which re-lexes only the tokens affected by
+/// the edit.
+///
 #[derive(Debug)]
 pub struct LexerOfString<P, T, E>
 where
@@ -45,7 +89,7 @@ where
 impl<P, T, E> Default for LexerOfString<P, T, E>
 where
     P: PosnInCharStream,
-    T: Sized + std::fmt::Debug + Copy,
+    T: Sized + std::fmt::Debug,
     E: LexerError<P>,
 {
     fn default() -> Self {
@@ -62,7 +106,7 @@ where
 impl<P, T, E> LexerOfString<P, T, E>
 where
     P: PosnInCharStream,
-    T: Sized + std::fmt::Debug + Copy,
+    T: Sized + std::fmt::Debug,
     E: LexerError<P>,
 {
     //cp set_text
@@ -88,10 +132,180 @@ where
 
     //mp lexer
     /// Create a [LexerOfStr] that will parse the text
-    pub fn lexer(&self) -> LexerOfStr<P, T, E> {
+    pub fn lexer(&self) -> LexerOfStr<'_, P, T, E> {
         LexerOfStr::new(&self.text)
     }
 
+    //mp edit
+    /// Replace a byte range of the text with another string, updating
+    /// the start and number of columns of just the lines affected
+    ///
+    /// The [TextEdit] returned describes the edit for
+    /// [LexerOfString::relex]
+    ///
+    /// Panics if the range is not within the text on character
+    /// boundaries
+    pub fn edit(&mut self, range: Range<usize>, replacement: &str) -> TextEdit {
+        let edit = TextEdit {
+            start: range.start,
+            old_end: range.end,
+            new_end: range.start + replacement.len(),
+        };
+        self.text.replace_range(range, replacement);
+        if self.line_start_ncolumns.is_empty() {
+            self.find_line_starts();
+        } else {
+            self.update_line_starts(&edit);
+        }
+        edit
+    }
+
+    //mp relex
+    /// Update the tokens of the text, with their spans (as delivered
+    /// by [Lexer::iter_spanned]) for an edit of the text by
+    /// [LexerOfString::edit], returning the range of the tokens that
+    /// were replaced
+    ///
+    /// The tokens must be all those of the text before the edit
+    /// (including whitespace and any other trivia). Lexing restarts at
+    /// the start of the token that ends at or after the start of the
+    /// edit, and stops once a token ends after the edit at the start of
+    /// one of the old tokens - where the token stream re-synchronises
+    /// with the old one. The old tokens from there on are retained,
+    /// with their positions moved by the change in byte offset, line
+    /// and (for those on the line of the re-synchronisation) column,
+    /// using [crate::UserPosn::offset]; if the position type does not
+    /// support this then the positions are found by stepping over the
+    /// text of the tokens (but without lexing it).
+    ///
+    /// This is only valid for a lexer whose tokens depend on the text
+    /// from their start, and not on the text preceding them.
+    ///
+    /// If the lexer returns an error then the error is returned, and
+    /// the tokens are truncated to those that precede the edit (which
+    /// remain valid for the new text); the text from there on must
+    /// then be lexed again from the end of the last token
+    pub fn relex<'a>(
+        &'a self,
+        tokens: &mut Vec<(StreamCharSpan<P>, T)>,
+        edit: &TextEdit,
+        parsers: &[BoxDynLexerParseFn<'a, LexerOfStr<'a, P, T, E>>],
+    ) -> Result<Range<usize>, E> {
+        let lexer = self.lexer();
+        let first = tokens.partition_point(|(span, _)| span.end().byte_ofs() < edit.start);
+        let mut state = match (tokens.get(first), tokens.last()) {
+            (Some((span, _)), _) => *span.start(),
+            (None, Some((span, _))) => *span.end(),
+            (None, None) => P::default(),
+        };
+        let mut new_tokens = Vec::new();
+        let mut old = first;
+        loop {
+            let parsed = match lexer.parse(state, parsers) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    tokens.truncate(first);
+                    return Err(e);
+                }
+            };
+            let Some((end, token)) = parsed else {
+                old = tokens.len();
+                break;
+            };
+            new_tokens.push((StreamCharSpan::new(state, end), token));
+            state = end;
+            if end.byte_ofs() < edit.new_end {
+                continue;
+            }
+            let old_ofs = end.byte_ofs() - edit.new_end + edit.old_end;
+            while tokens
+                .get(old)
+                .map_or(false, |(span, _)| span.start().byte_ofs() < old_ofs)
+            {
+                old += 1;
+            }
+            if tokens
+                .get(old)
+                .map_or(false, |(span, _)| span.start().byte_ofs() == old_ofs)
+            {
+                break;
+            }
+        }
+        let changed = first..(first + new_tokens.len());
+        let rest = tokens.split_off(old);
+        tokens.truncate(first);
+        tokens.extend(new_tokens);
+        let Some((resync, _)) = rest.first() else {
+            return Ok(changed);
+        };
+
+        // The re-synchronisation point is 'resync' in the old text and
+        // 'state' in the new text
+        let resync = *resync.start();
+        let bytes = state.byte_ofs() as isize - resync.byte_ofs() as isize;
+        let lines = state.line() as isize - resync.line() as isize;
+        let columns = state.column() as isize - resync.column() as isize;
+        let offset = |p: &P| {
+            let columns = if p.line() == resync.line() {
+                columns
+            } else {
+                0
+            };
+            p.offset(bytes, lines, columns)
+        };
+        tokens.reserve(rest.len());
+        for (span, token) in rest {
+            let span = match (offset(span.start()), offset(span.end())) {
+                (Some(start), Some(end)) => StreamCharSpan::new(start, end),
+                _ => {
+                    let start = state.byte_ofs();
+                    let len = span.byte_range().len();
+                    let num_chars = self.text[start..start + len].chars().count();
+                    StreamCharSpan::new(state, lexer.consumed(state, num_chars))
+                }
+            };
+            state = *span.end();
+            tokens.push((span, token));
+        }
+        Ok(changed)
+    }
+
+    //mi update_line_starts
+    /// Update the start byte offset and number of columns of the lines
+    /// affected by an edit of the text
+    ///
+    /// The lines from that containing the start of the edit up to that
+    /// containing the end of the edit are found again from the text;
+    /// the lines following them are unchanged in length, so their
+    /// starts just move on by the same lengths as before
+    fn update_line_starts(&mut self, edit: &TextEdit) {
+        let mut lines = std::mem::take(&mut self.line_start_ncolumns);
+        let first = lines[1..].partition_point(|(p, _)| p.byte_ofs() <= edit.start);
+        let after = 1 + lines[1..].partition_point(|(p, _)| p.byte_ofs() <= edit.old_end);
+        let tail = lines.split_off(after);
+        let mut pos = lines[first].0;
+        lines.truncate(first);
+        let scan_end = tail.first().map_or(self.text.len(), |(p, _)| {
+            p.byte_ofs() - edit.old_end + edit.new_end
+        });
+        let mut s = &self.text[pos.byte_ofs()..scan_end];
+        while let Some((line, next_line)) = s.split_once('\n') {
+            lines.push((pos, line.chars().count()));
+            pos = pos.advance_line(line.len() + 1);
+            s = next_line;
+        }
+        if tail.is_empty() {
+            lines.push((pos, s.chars().count()));
+        }
+        for (i, (p, ncolumns)) in tail.iter().enumerate() {
+            lines.push((pos, *ncolumns));
+            if let Some((next_p, _)) = tail.get(i + 1) {
+                pos = pos.advance_line(next_p.byte_ofs() - p.byte_ofs());
+            }
+        }
+        self.line_start_ncolumns = lines;
+    }
+
     //mi find_line_starts
     /// Finds the start byte offset and number of columns for all the
     /// lines in the text
@@ -117,13 +331,17 @@ where
 impl<P, T, E> FmtContext<P> for LexerOfString<P, T, E>
 where
     P: PosnInCharStream,
-    T: Sized + std::fmt::Debug + Copy,
+    T: Sized + std::fmt::Debug,
     E: LexerError<P>,
 {
     fn line_length(&self, line: usize) -> usize {
         self.line_start_ncolumns[line].1
     }
 
+    fn num_lines(&self) -> Option<usize> {
+        Some(self.line_start_ncolumns.len().saturating_sub(1))
+    }
+
     fn fmt_line(&self, f: &mut dyn std::fmt::Write, line: usize) -> std::fmt::Result {
         let s = &self.text[self.line_start_ncolumns[line].0.byte_ofs()..];
         let s = s.split_once('\n').map(|(s, _)| s).unwrap_or(s);
@@ -0,0 +1,257 @@
+//a Imports
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::{BoxDynLexerParseFn, Interner};
+use crate::{CharStream, Lexer, LexerError, LexerOfStr, LexerParseResult};
+use crate::{ModalPosn, ParserIterator, PosnInCharStream, StreamCharSpan};
+
+//a ModeStacks
+//ti ModeStacks
+/// The stacks of modes of a [ModalLexer]
+///
+/// Each stack is an entry holding the mode at the top of the stack
+/// and the identifier of the stack beneath it; stack 0 is the
+/// initial stack of just mode 0. Stacks are never removed, so that
+/// the identifier in any [ModalPosn] remains valid, and pushing the
+/// same mode on the same stack reuses the existing entry.
+#[derive(Debug)]
+struct ModeStacks {
+    entries: Vec<(usize, Option<usize>)>,
+    index: HashMap<(usize, usize), usize>,
+}
+
+//ii Default for ModeStacks
+impl Default for ModeStacks {
+    fn default() -> Self {
+        Self {
+            entries: vec![(0, None)],
+            index: HashMap::new(),
+        }
+    }
+}
+
+//ii ModeStacks
+impl ModeStacks {
+    //mi push
+    /// Get the identifier of the stack with a mode pushed on another
+    fn push(&mut self, stack: usize, mode: usize) -> usize {
+        let entries = &mut self.entries;
+        *self.index.entry((stack, mode)).or_insert_with(|| {
+            entries.push((mode, Some(stack)));
+            entries.len() - 1
+        })
+    }
+}
+
+//a ModalLexer
+//tp ModalLexer
+/// A [Lexer] of a [str] with a stack of modes, each of which has its
+/// own parse functions, for context-sensitive lexing (such as string
+/// interpolation, heredocs, or template languages)
+///
+/// The lexer is created with the parse functions for its initial mode
+/// (mode 0); further modes are added with [ModalLexer::add_mode].
+/// When parsing a token, the parse functions of the mode at the top
+/// of the stack are tried in turn, followed by those supplied to
+/// [Lexer::parse] or [Lexer::iter] (which are hence common to all
+/// modes).
+///
+/// The state of the lexer is a [ModalPosn], which is the position in
+/// the text and an identifier of the stack of modes; it is [Copy], so
+/// that it may be saved and restored as for any other lexer. A parse
+/// function changes mode by returning a state from
+/// [ModalLexer::push_mode] or [ModalLexer::pop_mode].
+///
+/// The [ModalLexer] is also a [CharStream], so the parse functions of
+/// the [crate::parsers] module may be used for its modes.
+pub struct ModalLexer<'a, P, T, E>
+where
+    P: PosnInCharStream,
+    T: Sized + std::fmt::Debug,
+    E: LexerError<ModalPosn<P>>,
+{
+    text: LexerOfStr<'a, ModalPosn<P>, T, E>,
+    modes: Vec<Vec<BoxDynLexerParseFn<'a, Self>>>,
+    stacks: RefCell<ModeStacks>,
+}
+
+//ip Debug for ModalLexer
+impl<'a, P, T, E> std::fmt::Debug for ModalLexer<'a, P, T, E>
+where
+    P: PosnInCharStream,
+    T: Sized + std::fmt::Debug,
+    E: LexerError<ModalPosn<P>>,
+{
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        fmt.debug_struct("ModalLexer")
+            .field("num_modes", &self.modes.len())
+            .field("stacks", &self.stacks)
+            .finish()
+    }
+}
+
+//ip ModalLexer
+impl<'a, P, T, E> ModalLexer<'a, P, T, E>
+where
+    P: PosnInCharStream,
+    T: Sized + std::fmt::Debug,
+    E: LexerError<ModalPosn<P>>,
+{
+    //fp new
+    /// Create a new [ModalLexer] by borrowing a [str], with the parse
+    /// functions for the initial mode (mode 0)
+    pub fn new(text: &'a str, parsers: Vec<BoxDynLexerParseFn<'a, Self>>) -> Self {
+        Self {
+            text: LexerOfStr::new(text),
+            modes: vec![parsers],
+            stacks: RefCell::new(ModeStacks::default()),
+        }
+    }
+
+    //cp with_interner
+    /// Set the [Interner] of the lexer, which parse functions may use
+    /// through [CharStream::interner] to intern the text of tokens
+    pub fn with_interner(mut self, interner: &'a Interner) -> Self {
+        self.text = self.text.with_interner(interner);
+        self
+    }
+
+    //cp with_start
+    /// Set the position at the start of the text, from which the
+    /// iterators of the [Lexer] begin; see [LexerOfStr::with_start]
+    pub fn with_start(mut self, start: P) -> Self {
+        self.text = self.text.with_start(ModalPosn::new(start, 0));
+        self
+    }
+
+    //mp add_mode
+    /// Add a mode with its parse functions, returning the mode
+    pub fn add_mode(&mut self, parsers: Vec<BoxDynLexerParseFn<'a, Self>>) -> usize {
+        self.modes.push(parsers);
+        self.modes.len() - 1
+    }
+
+    //ap mode
+    /// Get the mode at the top of the stack of a state
+    pub fn mode(&self, state: &ModalPosn<P>) -> usize {
+        self.stacks.borrow().entries[state.stack()].0
+    }
+
+    //ap depth
+    /// Get the number of modes pushed on the initial mode in a state
+    pub fn depth(&self, state: &ModalPosn<P>) -> usize {
+        let stacks = self.stacks.borrow();
+        let mut depth = 0;
+        let mut stack = state.stack();
+        while let Some(below) = stacks.entries[stack].1 {
+            depth += 1;
+            stack = below;
+        }
+        depth
+    }
+
+    //mp push_mode
+    /// Get the state with a mode pushed on its stack of modes
+    ///
+    /// Panics if the mode has not been added to the lexer
+    pub fn push_mode(&self, state: ModalPosn<P>, mode: usize) -> ModalPosn<P> {
+        assert!(mode < self.modes.len(), "Mode {} is not known", mode);
+        let stack = self.stacks.borrow_mut().push(state.stack(), mode);
+        ModalPosn::new(state.posn(), stack)
+    }
+
+    //mp pop_mode
+    /// Get the state with the top mode popped from its stack of
+    /// modes, or None if the stack holds only the initial mode
+    pub fn pop_mode(&self, state: ModalPosn<P>) -> Option<ModalPosn<P>> {
+        let below = self.stacks.borrow().entries[state.stack()].1;
+        below.map(|stack| ModalPosn::new(state.posn(), stack))
+    }
+}
+
+//a Impl Lexer, CharStream
+//ip Lexer for ModalLexer
+impl<'a, P, T, E> Lexer for ModalLexer<'a, P, T, E>
+where
+    P: PosnInCharStream,
+    T: Sized + std::fmt::Debug,
+    E: LexerError<ModalPosn<P>>,
+{
+    type Token = T;
+    type Error = E;
+    type State = ModalPosn<P>;
+
+    //mp parse
+    fn parse<'iter>(
+        &'iter self,
+        state: Self::State,
+        parsers: &[BoxDynLexerParseFn<'iter, Self>],
+    ) -> LexerParseResult<Self::State, Self::Token, Self::Error> {
+        if let Some(ch) = self.peek_at(&state) {
+            let mode = self.mode(&state);
+            for p in self.modes[mode].iter().chain(parsers.iter()) {
+                let result = p(self, state, ch)?;
+                if result.is_some() {
+                    return Ok(result);
+                }
+            }
+            return Err(E::failed_to_parse(state, ch));
+        }
+        Ok(None)
+    }
+
+    //mp start
+    fn start(&self) -> Self::State {
+        self.text.start()
+    }
+
+    //mp iter
+    fn iter<'iter>(
+        &'iter self,
+        parsers: &'iter [BoxDynLexerParseFn<'iter, Self>],
+    ) -> Box<dyn Iterator<Item = Result<T, E>> + 'iter> {
+        Box::new(ParserIterator::new(self, self.start(), parsers))
+    }
+}
+
+//ip CharStream for ModalLexer
+impl<'a, P, T, E> CharStream<ModalPosn<P>> for ModalLexer<'a, P, T, E>
+where
+    P: PosnInCharStream,
+    T: Sized + std::fmt::Debug,
+    E: LexerError<ModalPosn<P>>,
+{
+    fn range_as_bytes(&self, ofs: usize, n: usize) -> &[u8] {
+        self.text.range_as_bytes(ofs, n)
+    }
+    fn get_text_span(&self, span: &StreamCharSpan<ModalPosn<P>>) -> &str {
+        self.text.get_text_span(span)
+    }
+    fn get_text(&self, start: ModalPosn<P>, end: ModalPosn<P>) -> &str {
+        self.text.get_text(start, end)
+    }
+    fn peek_at(&self, state: &ModalPosn<P>) -> Option<char> {
+        self.text.peek_at(state)
+    }
+    fn interner(&self) -> Option<&Interner> {
+        self.text.interner()
+    }
+    fn matches_bytes(&self, state: &ModalPosn<P>, s: &[u8]) -> bool {
+        self.text.matches_bytes(state, s)
+    }
+    fn matches_str(&self, pos: &ModalPosn<P>, pat: &str) -> bool {
+        self.text.matches_str(pos, pat)
+    }
+    fn consumed(&self, state: ModalPosn<P>, n: usize) -> ModalPosn<P> {
+        self.text.consumed(state, n)
+    }
+    fn do_while<F: Fn(usize, char) -> bool>(
+        &self,
+        state: ModalPosn<P>,
+        ch: char,
+        f: &F,
+    ) -> (ModalPosn<P>, Option<(ModalPosn<P>, usize)>) {
+        self.text.do_while(state, ch, f)
+    }
+}
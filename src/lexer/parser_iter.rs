@@ -1,11 +1,33 @@
 //a Imports
-use crate::BoxDynLexerParseFn;
-use crate::Lexer;
+use std::iter::FusedIterator;
+
+use crate::{BoxDynLexerParseFn, BoxDynLexerRecoveryFn};
+use crate::{CharStream, Lexer, LexerRecovery};
+use crate::{PosnInCharStream, StreamCharSpan};
 
 //a ParserIterator
+//ti StatesAndToken
+/// The start and end states of a token parsed by a Lexer, with the token
+type StatesAndToken<L> = (
+    <L as Lexer>::State,
+    <L as Lexer>::State,
+    <L as Lexer>::Token,
+);
+
 //tp ParserIterator
 /// An iterator over a Lexer presenting the parsed Tokens from it
-
+///
+/// The iterator returns None when the end of the stream is reached;
+/// it is fused, so that it continues to return None from then on.
+///
+/// If a parse error occurs then the error is returned by the
+/// iterator. By default the iterator then stops - returning None
+/// from then on, with its state remaining at the point of the
+/// error. If the iterator has been given a [LexerRecovery] then
+/// parsing instead resumes at the state that the recovery provides,
+/// so (for example) [LexerRecovery::SkipChar] resumes one character
+/// beyond the error. Should the recovery fail (or not advance the
+/// state) then the iterator stops.
 pub struct ParserIterator<'a, L>
 where
     L: Lexer,
@@ -13,6 +35,8 @@ where
     lexer: &'a L,
     state: L::State,
     parsers: &'a [BoxDynLexerParseFn<'a, L>],
+    recovery: Option<BoxDynLexerRecoveryFn<'a, L>>,
+    finished: bool,
 }
 
 //ip ParserIterator
@@ -27,6 +51,76 @@ where
             lexer,
             state,
             parsers,
+            recovery: None,
+            finished: false,
+        }
+    }
+
+    //ap state
+    /// Get the current state of the iterator within the [Lexer] stream
+    ///
+    /// This is the state after the last token that was returned; if
+    /// the iterator has stopped because of an error, it is the
+    /// state at which the error occurred.
+    pub fn state(&self) -> L::State {
+        self.state
+    }
+
+    //cp with_recovery
+    /// Set the iterator to recover from parse errors using a
+    /// [LexerRecovery]; after returning an error, the iterator will
+    /// resume parsing at the state provided by the recovery
+    pub fn with_recovery(mut self, recovery: LexerRecovery<'a, L>) -> Self
+    where
+        L: CharStream<<L as Lexer>::State>,
+        L::State: PosnInCharStream,
+    {
+        self.recovery = Some(Box::new(move |lexer, state| {
+            recovery
+                .recover(lexer, state)
+                .filter(|s| s.byte_ofs() > state.byte_ofs())
+        }));
+        self
+    }
+
+    //mp spanned
+    /// Convert the iterator into one that delivers each token with
+    /// the span of the stream that it was parsed from
+    pub fn spanned(self) -> SpannedParserIterator<'a, L>
+    where
+        L::State: PosnInCharStream,
+    {
+        SpannedParserIterator(self)
+    }
+
+    //mi next_token
+    /// Parse the next token, returning the start and end states of
+    /// the token with the token itself
+    fn next_token(&mut self) -> Option<Result<StatesAndToken<L>, L::Error>> {
+        if self.finished {
+            return None;
+        }
+        match self.lexer.parse(self.state, self.parsers) {
+            Err(e) => {
+                let recovered = self
+                    .recovery
+                    .as_ref()
+                    .and_then(|recovery| recovery(self.lexer, self.state));
+                match recovered {
+                    Some(state) => self.state = state,
+                    None => self.finished = true,
+                }
+                Some(Err(e))
+            }
+            Ok(Some((state, token))) => {
+                let start = self.state;
+                self.state = state;
+                Some(Ok((start, state, token)))
+            }
+            _ => {
+                self.finished = true;
+                None
+            }
         }
     }
 }
@@ -39,13 +133,59 @@ where
 {
     type Item = Result<L::Token, L::Error>;
     fn next(&mut self) -> Option<Self::Item> {
-        match self.lexer.parse(self.state, self.parsers) {
-            Err(e) => Some(Err(e)),
-            Ok(Some((state, token))) => {
-                self.state = state;
-                Some(Ok(token))
-            }
-            _ => None,
-        }
+        self.next_token().map(|r| r.map(|(_, _, token)| token))
+    }
+}
+
+//ip FusedIterator for ParserIterator
+impl<'a, L> FusedIterator for ParserIterator<'a, L> where L: Lexer {}
+
+//a SpannedParserIterator
+//tp SpannedParserIterator
+/// An iterator over a Lexer presenting the parsed Tokens from it,
+/// each with the [StreamCharSpan] of the text that it was parsed from
+///
+/// The span can be used to provide context for the token (such as
+/// with [crate::FmtContext::fmt_context]), or to retrieve the text of
+/// the token from the stream.
+///
+/// This is created with [ParserIterator::spanned] or
+/// [Lexer::iter_spanned]
+pub struct SpannedParserIterator<'a, L>(ParserIterator<'a, L>)
+where
+    L: Lexer;
+
+//ip SpannedParserIterator
+impl<'a, L> SpannedParserIterator<'a, L>
+where
+    L: Lexer,
+{
+    //ap state
+    /// Get the current state of the iterator within the [Lexer]
+    /// stream; see [ParserIterator::state]
+    pub fn state(&self) -> L::State {
+        self.0.state()
+    }
+}
+
+//ip Iterator for SpannedParserIterator
+impl<'a, L> Iterator for SpannedParserIterator<'a, L>
+where
+    L: Lexer,
+    L::State: PosnInCharStream,
+{
+    type Item = Result<(StreamCharSpan<L::State>, L::Token), L::Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0
+            .next_token()
+            .map(|r| r.map(|(start, end, token)| (StreamCharSpan::new(start, end), token)))
     }
 }
+
+//ip FusedIterator for SpannedParserIterator
+impl<'a, L> FusedIterator for SpannedParserIterator<'a, L>
+where
+    L: Lexer,
+    L::State: PosnInCharStream,
+{
+}
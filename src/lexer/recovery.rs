@@ -0,0 +1,70 @@
+//a Imports
+use crate::{BoxDynLexerRecoveryFn, CharStream, Lexer, PosnInCharStream};
+
+//a LexerRecovery
+//tp LexerRecovery
+/// A strategy for recovering from an error returned by a [Lexer]
+/// parse, so that lexing can resume beyond the error
+///
+/// When none of the parser functions of a [Lexer] match the character
+/// at a stream state (or a parser function returns an error), the
+/// [Lexer] returns an error. Without recovery the stream state
+/// cannot be advanced, and so only the first error in a stream can
+/// be reported.
+///
+/// With a [LexerRecovery] the stream state at the error is mapped to
+/// a new state beyond the error, at which lexing can continue - so
+/// that all the errors in a stream can be reported in a single run.
+///
+/// Recovery can be used directly after an error from [Lexer::parse],
+/// using [LexerRecovery::recover] to generate the state to resume
+/// parsing at, or applied to a token iterator using
+/// [Lexer::iter_recovering] or [crate::ParserIterator::with_recovery]
+pub enum LexerRecovery<'a, L>
+where
+    L: Lexer,
+{
+    /// Skip the single character at which the error occurred
+    SkipChar,
+    /// Skip the character at which the error occurred and any
+    /// subsequent characters up to (but not including) one of the
+    /// synchronization characters (or the end of the stream)
+    SkipTo(&'a [char]),
+    /// Invoke a function to determine the state at which to resume
+    /// lexing, given the state of the error; if this returns None
+    /// then recovery is not possible
+    Custom(BoxDynLexerRecoveryFn<'a, L>),
+}
+
+//ip LexerRecovery
+impl<'a, L> LexerRecovery<'a, L>
+where
+    L: Lexer + CharStream<<L as Lexer>::State>,
+    L::State: PosnInCharStream,
+{
+    //mp recover
+    /// Determine the stream state at which to resume lexing, given
+    /// the state at which an error occurred
+    ///
+    /// None is returned if recovery is not possible; this is the case
+    /// at the end of the stream, for example.
+    pub fn recover(&self, lexer: &L, state: L::State) -> Option<L::State> {
+        match self {
+            Self::SkipChar => lexer
+                .peek_at(&state)
+                .map(|ch| lexer.consumed_char(state, ch)),
+            Self::SkipTo(sync) => {
+                let ch = lexer.peek_at(&state)?;
+                let mut state = lexer.consumed_char(state, ch);
+                while let Some(ch) = lexer.peek_at(&state) {
+                    if sync.contains(&ch) {
+                        break;
+                    }
+                    state = lexer.consumed_char(state, ch);
+                }
+                Some(state)
+            }
+            Self::Custom(f) => f(lexer, state),
+        }
+    }
+}
@@ -1,6 +1,26 @@
 //a Imports
 use crate::{LexerError, UserPosn};
 
+//a SimpleParseErrorKind
+//tp SimpleParseErrorKind
+/// The kind of a [SimpleParseError]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SimpleParseErrorKind {
+    /// The character could not be matched to a token
+    #[default]
+    FailedToParse,
+    /// The token started with the character (such as a quote) is not
+    /// terminated
+    Unterminated,
+    /// The escape sequence with the character is invalid
+    BadEscape,
+    /// The indentation of the line starting with the character does
+    /// not match that of any enclosing line
+    BadIndent,
+    /// Reading the stream failed, so it is truncated at the position
+    ReadError,
+}
+
 //a SimpleParseError
 //tp SimpleParseError
 /// A simple implementation of a type supporting LexerError
@@ -18,6 +38,28 @@ where
 
     /// The position of the character in the stream
     pub pos: P,
+
+    /// The kind of the error
+    kind: SimpleParseErrorKind,
+}
+
+//ip SimpleParseError
+impl<P> SimpleParseError<P>
+where
+    P: UserPosn,
+{
+    //fp new
+    /// Create a new [SimpleParseError] of a kind, at a position with
+    /// the character there
+    pub fn new(kind: SimpleParseErrorKind, pos: P, ch: char) -> Self {
+        Self { ch, pos, kind }
+    }
+
+    //ap kind
+    /// Get the kind of the error
+    pub fn kind(&self) -> SimpleParseErrorKind {
+        self.kind
+    }
 }
 
 //ip Error for SimpleParseError
@@ -29,7 +71,25 @@ where
     P: UserPosn,
 {
     fn failed_to_parse(pos: P, ch: char) -> Self {
-        Self { ch, pos }
+        let kind = SimpleParseErrorKind::FailedToParse;
+        Self { ch, pos, kind }
+    }
+    fn unterminated(pos: P, ch: char) -> Self {
+        let kind = SimpleParseErrorKind::Unterminated;
+        Self { ch, pos, kind }
+    }
+    fn bad_escape(pos: P, ch: char) -> Self {
+        let kind = SimpleParseErrorKind::BadEscape;
+        Self { ch, pos, kind }
+    }
+    fn bad_indent(pos: P, ch: char) -> Self {
+        let kind = SimpleParseErrorKind::BadIndent;
+        Self { ch, pos, kind }
+    }
+    fn read_error(pos: P, _error: &std::io::Error) -> Self {
+        let kind = SimpleParseErrorKind::ReadError;
+        let ch = char::REPLACEMENT_CHARACTER;
+        Self { ch, pos, kind }
     }
 }
 
@@ -39,7 +99,21 @@ where
     P: UserPosn,
 {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        write!(fmt, "Failed to parse: unexpected char '{}' at ", self.ch)?;
+        match self.kind {
+            SimpleParseErrorKind::FailedToParse => {
+                write!(fmt, "Failed to parse: unexpected char '{}' at ", self.ch)?
+            }
+            SimpleParseErrorKind::Unterminated => {
+                write!(fmt, "Failed to parse: unterminated '{}' at ", self.ch)?
+            }
+            SimpleParseErrorKind::BadEscape => {
+                write!(fmt, "Failed to parse: bad escape '\\{}' at ", self.ch)?
+            }
+            SimpleParseErrorKind::BadIndent => {
+                write!(fmt, "Failed to parse: inconsistent indentation at ")?
+            }
+            SimpleParseErrorKind::ReadError => write!(fmt, "Failed to read the stream at ")?,
+        }
         self.pos.error_fmt(fmt)
     }
 }
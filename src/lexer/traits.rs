@@ -1,4 +1,6 @@
 //a Imports
+use crate::{CharStream, IndentIterator, LexerRecovery, PosnInCharStream};
+use crate::{ParserIterator, SpannedParserIterator};
 
 //a LexerError
 //tt LexerError
@@ -15,6 +17,46 @@ pub trait LexerError<P>: Sized + std::error::Error {
     /// matched for a token) has occurred at the position indicated by
     /// the state
     fn failed_to_parse(state: P, ch: char) -> Self;
+
+    /// Return an error indicating that a token that was started at
+    /// the position indicated by the state, with the character 'ch'
+    /// (such as the opening quote of a string), is not terminated
+    /// before the end of the stream
+    ///
+    /// The default is to return a 'failed_to_parse' error
+    fn unterminated(state: P, ch: char) -> Self {
+        Self::failed_to_parse(state, ch)
+    }
+
+    /// Return an error indicating that an escape sequence (such as
+    /// in a string) at the position indicated by the state is
+    /// invalid; 'ch' is the character following the escape character
+    ///
+    /// The default is to return a 'failed_to_parse' error
+    fn bad_escape(state: P, ch: char) -> Self {
+        Self::failed_to_parse(state, ch)
+    }
+
+    /// Return an error indicating that the indentation of the line
+    /// whose first token starts at the position indicated by the
+    /// state, with the character 'ch', does not match that of any
+    /// enclosing line (see [crate::IndentIterator])
+    ///
+    /// The default is to return a 'failed_to_parse' error
+    fn bad_indent(state: P, ch: char) -> Self {
+        Self::failed_to_parse(state, ch)
+    }
+
+    /// Return an error indicating that reading the stream failed at
+    /// the position indicated by the state (such as an I/O error, or
+    /// data that is not valid UTF8), so that the stream has been
+    /// truncated there
+    ///
+    /// The default is to return a 'failed_to_parse' error with the
+    /// Unicode replacement character
+    fn read_error(state: P, _error: &std::io::Error) -> Self {
+        Self::failed_to_parse(state, char::REPLACEMENT_CHARACTER)
+    }
 }
 
 //a Lexer
@@ -100,24 +142,97 @@ pub trait Lexer: std::fmt::Debug {
     /// This attempts to parse the next token found at the state of
     /// the [Lexer] stream, by applying the parsers in order.
     ///
-    /// An error is returned if the token cannot be parsed
+    /// An error is returned if the token cannot be parsed; a
+    /// [LexerRecovery] can be used to determine a state beyond the
+    /// error at which parsing can be resumed
     fn parse<'a>(
         &'a self,
         state: Self::State,
         parsers: &[BoxDynLexerParseFn<'a, Self>],
     ) -> LexerParseResult<Self::State, Self::Token, Self::Error>;
 
+    /// Get the state at the start of the [Lexer] stream, from which
+    /// the iterators begin
+    ///
+    /// The default is the default State
+    fn start(&self) -> Self::State {
+        Default::default()
+    }
+
     /// This creates an iterator over all of the tokens in the [Lexer]
     /// stream, by applying the parsers in order at the current stream
     /// position whenever the 'next' method is invoked.
     ///
     /// The iterator returns None when the end of stream is reached,
     /// otherwise it returns a result of the token or an error,
-    /// depending on the success of the parsers.
+    /// depending on the success of the parsers. After an error the
+    /// iterator stops, returning None thereafter (see
+    /// [ParserIterator]).
     fn iter<'iter>(
         &'iter self,
         parsers: &'iter [BoxDynLexerParseFn<'iter, Self>],
     ) -> Box<dyn Iterator<Item = Result<Self::Token, Self::Error>> + 'iter>;
+
+    /// This creates an iterator over all of the tokens in the [Lexer]
+    /// stream in the same manner as 'iter', except that each token
+    /// is delivered with the [crate::StreamCharSpan] of the stream
+    /// that it was parsed from
+    ///
+    /// This is available if the [Lexer] State is a [PosnInCharStream]
+    fn iter_spanned<'iter>(
+        &'iter self,
+        parsers: &'iter [BoxDynLexerParseFn<'iter, Self>],
+    ) -> SpannedParserIterator<'iter, Self>
+    where
+        Self: Sized,
+        Self::State: PosnInCharStream,
+    {
+        let state = self.start();
+        ParserIterator::new(self, state, parsers).spanned()
+    }
+
+    /// This creates an iterator over all of the tokens in the [Lexer]
+    /// stream in the same manner as 'iter', except that when an error
+    /// occurs the iterator returns the error and then uses the
+    /// [LexerRecovery] to resume parsing beyond the error
+    ///
+    /// This permits all of the errors in a stream to be reported,
+    /// rather than just the first.
+    ///
+    /// This is available if the [Lexer] is also a [CharStream]
+    fn iter_recovering<'iter>(
+        &'iter self,
+        parsers: &'iter [BoxDynLexerParseFn<'iter, Self>],
+        recovery: LexerRecovery<'iter, Self>,
+    ) -> ParserIterator<'iter, Self>
+    where
+        Self: Sized + CharStream<<Self as Lexer>::State>,
+        Self::State: PosnInCharStream,
+    {
+        let state = self.start();
+        ParserIterator::new(self, state, parsers).with_recovery(recovery)
+    }
+
+    /// This creates an iterator over all of the tokens in the [Lexer]
+    /// stream for an indentation-sensitive language, delivering the
+    /// tokens that are not trivia (as identified by 'is_trivia')
+    /// together with synthetic tokens for the ends of lines and
+    /// changes in indentation; see [IndentIterator]
+    ///
+    /// This is available if the [Lexer] is also a [CharStream]
+    fn iter_indented<'iter>(
+        &'iter self,
+        parsers: &'iter [BoxDynLexerParseFn<'iter, Self>],
+        is_trivia: &'iter dyn Fn(&Self::Token) -> bool,
+    ) -> IndentIterator<'iter, Self>
+    where
+        Self: Sized + CharStream<<Self as Lexer>::State>,
+        Self::State: PosnInCharStream,
+    {
+        let state = self.start();
+        let tokens = ParserIterator::new(self, state, parsers).spanned();
+        IndentIterator::new(self, tokens, is_trivia)
+    }
 }
 
 //tp LexerParseResult
@@ -125,8 +240,10 @@ pub trait Lexer: std::fmt::Debug {
 ///
 /// This *could* have been defined as:
 ///
+/// ```ignore
 ///    pub type LexerParseResult<L:Lexer>
 ///      = Result<Option<(<L as Lexer>::State, <L as Lexer>::Token)>, <L as Lexer>::Error>;
+/// ```
 ///
 /// But then clients that have their type L with a lifetime (which is common) would have a parse
 /// result that must be indicated by a lifetime, where the actual result *does not*.
@@ -171,3 +288,15 @@ pub type BoxDynLexerParseFn<'a, L> = Box<
             <L as Lexer>::Error,
         > + 'a,
 >;
+
+//tp BoxDynLexerRecoveryFn
+/// The type of an error recovery function, when Boxed as a dyn trait
+///
+/// An error recovery function is invoked with the [Lexer] and the
+/// state at which a parse error occurred; it returns the state at
+/// which lexing should resume (which must be beyond the error state)
+/// or None if recovery is not possible.
+///
+/// See [LexerRecovery] for how this is used
+pub type BoxDynLexerRecoveryFn<'a, L> =
+    Box<dyn for<'call> Fn(&'call L, <L as Lexer>::State) -> Option<<L as Lexer>::State> + 'a>;
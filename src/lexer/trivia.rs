@@ -0,0 +1,248 @@
+//a Imports
+use crate::{BoxDynLexerParseFn, Lexer, PosnInCharStream, StreamCharSpan, UserPosn};
+
+//ti SpannedToken
+/// A token with the span of the stream it was parsed from
+type SpannedToken<P, T> = (StreamCharSpan<P>, T);
+
+//a TriviaToken
+//tp TriviaToken
+/// A significant token of a [TriviaLexer], with its span and the
+/// trivia tokens (such as whitespace and comments) attached to it
+///
+/// The trailing trivia of a token are those that follow it and end on
+/// the line on which the token ends; all other trivia are leading
+/// trivia of the next significant token. So a comment at the end of
+/// a line trails the token before it, and the newline and the
+/// indentation of the next line lead the first token of that line.
+///
+/// This requires a state of the [Lexer] that tracks lines (such as a
+/// [crate::StreamCharPos] of [crate::LineColumn]); with a state that
+/// does not (such as usize, whose line is always 0) the whole text is
+/// a single line, and so all the trivia that follow a significant
+/// token trail it.
+#[derive(Debug, Clone)]
+pub struct TriviaToken<P, T>
+where
+    P: PosnInCharStream,
+{
+    leading: Vec<SpannedToken<P, T>>,
+    span: StreamCharSpan<P>,
+    token: T,
+    trailing: Vec<SpannedToken<P, T>>,
+}
+
+//ip TriviaToken
+impl<P, T> TriviaToken<P, T>
+where
+    P: PosnInCharStream,
+{
+    //ap leading
+    /// Get the trivia (with their spans) that precede the token
+    pub fn leading(&self) -> &[SpannedToken<P, T>] {
+        &self.leading
+    }
+
+    //ap span
+    /// Get the span of the token itself
+    pub fn span(&self) -> &StreamCharSpan<P> {
+        &self.span
+    }
+
+    //ap token
+    /// Get the token
+    pub fn token(&self) -> &T {
+        &self.token
+    }
+
+    //ap trailing
+    /// Get the trivia (with their spans) that follow the token on the
+    /// line on which it ends
+    pub fn trailing(&self) -> &[SpannedToken<P, T>] {
+        &self.trailing
+    }
+
+    //ap full_span
+    /// Get the span of the token including its leading and trailing
+    /// trivia
+    pub fn full_span(&self) -> StreamCharSpan<P> {
+        let start = self.leading.first().map_or(&self.span, |(s, _)| s);
+        let end = self.trailing.last().map_or(&self.span, |(s, _)| s);
+        StreamCharSpan::new(*start.start(), *end.end())
+    }
+
+    //mi spans
+    /// Iterate over the spans of the leading trivia, the token, and
+    /// the trailing trivia, in order
+    fn spans(&self) -> impl Iterator<Item = &StreamCharSpan<P>> {
+        self.leading
+            .iter()
+            .map(|(s, _)| s)
+            .chain(std::iter::once(&self.span))
+            .chain(self.trailing.iter().map(|(s, _)| s))
+    }
+}
+
+//a TriviaTokens
+//tp TriviaTokens
+/// The complete tokens of a text lexed by a [TriviaLexer] - the
+/// significant tokens with their trivia attached, and the trivia at
+/// the end of the text that follows the last significant token
+///
+/// Every byte of the text is in the span of exactly one token or
+/// trivia, so the text may be reproduced exactly from the tokens; see
+/// [TriviaTokens::round_trip].
+#[derive(Debug, Clone)]
+pub struct TriviaTokens<P, T>
+where
+    P: PosnInCharStream,
+{
+    tokens: Vec<TriviaToken<P, T>>,
+    end_trivia: Vec<SpannedToken<P, T>>,
+}
+
+//ip TriviaTokens
+impl<P, T> TriviaTokens<P, T>
+where
+    P: PosnInCharStream,
+{
+    //ap tokens
+    /// Get the significant tokens, with their trivia
+    pub fn tokens(&self) -> &[TriviaToken<P, T>] {
+        &self.tokens
+    }
+
+    //ap end_trivia
+    /// Get the trivia (with their spans) at the end of the text that
+    /// are not trailing trivia of the last significant token
+    pub fn end_trivia(&self) -> &[SpannedToken<P, T>] {
+        &self.end_trivia
+    }
+
+    //mp spans
+    /// Iterate over the spans of all the tokens and trivia, in the
+    /// order of the text
+    pub fn spans(&self) -> impl Iterator<Item = &StreamCharSpan<P>> {
+        self.tokens
+            .iter()
+            .flat_map(|t| t.spans())
+            .chain(self.end_trivia.iter().map(|(s, _)| s))
+    }
+
+    //mp round_trip
+    /// Concatenate the text of all the tokens and trivia, which should
+    /// be the text that was lexed (such as [crate::LexerOfString::text])
+    ///
+    /// Panics if a span is not within the text
+    pub fn round_trip(&self, text: &str) -> String {
+        let mut s = String::with_capacity(text.len());
+        for span in self.spans() {
+            s.push_str(&text[span.byte_range()]);
+        }
+        s
+    }
+
+    //mp is_lossless
+    /// Return true if the spans of the tokens and trivia follow on from
+    /// each other and cover the whole of the text, so that
+    /// [TriviaTokens::round_trip] reproduces it exactly
+    pub fn is_lossless(&self, text: &str) -> bool {
+        let mut ofs = 0;
+        for span in self.spans() {
+            let range = span.byte_range();
+            if range.start != ofs || !text.is_char_boundary(range.end) {
+                return false;
+            }
+            ofs = range.end;
+        }
+        ofs == text.len()
+    }
+}
+
+//a TriviaLexer
+//tp TriviaLexer
+/// An adapter over a [Lexer] for formatters and refactoring tools,
+/// that accounts for every byte of the text
+///
+/// Tokens for which the 'is_trivia' predicate returns true (such as
+/// whitespace and comments) are attached as leading or trailing
+/// trivia to the significant tokens (see [TriviaToken]), with their
+/// exact spans, rather than being discarded.
+///
+/// As each token is parsed from the end of the previous one, and the
+/// text is lexed until the end of the stream, the spans of the
+/// [TriviaTokens] produced by [TriviaLexer::lex] are contiguous and
+/// cover the whole text.
+///
+/// The split of trivia into leading and trailing trivia depends on the
+/// lines of the state of the lexer; see [TriviaToken].
+pub struct TriviaLexer<'a, L>
+where
+    L: Lexer,
+{
+    lexer: &'a L,
+    is_trivia: &'a dyn Fn(&L::Token) -> bool,
+}
+
+//ip Debug for TriviaLexer
+impl<'a, L> std::fmt::Debug for TriviaLexer<'a, L>
+where
+    L: Lexer,
+{
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        fmt.debug_struct("TriviaLexer")
+            .field("lexer", self.lexer)
+            .finish()
+    }
+}
+
+//ip TriviaLexer
+impl<'a, L> TriviaLexer<'a, L>
+where
+    L: Lexer,
+    L::State: PosnInCharStream,
+{
+    //fp new
+    /// Create a new [TriviaLexer] for a lexer, with a predicate
+    /// identifying trivia tokens
+    pub fn new(lexer: &'a L, is_trivia: &'a dyn Fn(&L::Token) -> bool) -> Self {
+        Self { lexer, is_trivia }
+    }
+
+    //mp lex
+    /// Lex the whole text of the lexer with the given token parser
+    /// functions, attaching the trivia to the significant tokens
+    ///
+    /// The first error returned by the lexer is returned
+    pub fn lex<'p>(
+        &self,
+        parsers: &[BoxDynLexerParseFn<'p, L>],
+    ) -> Result<TriviaTokens<L::State, L::Token>, L::Error> {
+        let mut tokens: Vec<TriviaToken<L::State, L::Token>> = Vec::new();
+        let mut leading = Vec::new();
+        let mut trailing_line = None;
+        let mut state = self.lexer.start();
+        while let Some((end, token)) = self.lexer.parse(state, parsers)? {
+            let span = StreamCharSpan::new(state, end);
+            state = end;
+            if !(self.is_trivia)(&token) {
+                trailing_line = Some(end.line());
+                tokens.push(TriviaToken {
+                    leading: std::mem::take(&mut leading),
+                    span,
+                    token,
+                    trailing: Vec::new(),
+                });
+            } else if trailing_line == Some(end.line()) {
+                tokens.last_mut().unwrap().trailing.push((span, token));
+            } else {
+                trailing_line = None;
+                leading.push((span, token));
+            }
+        }
+        Ok(TriviaTokens {
+            tokens,
+            end_trivia: leading,
+        })
+    }
+}
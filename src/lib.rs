@@ -86,7 +86,15 @@ be static.
 # Tokens
 
 The token type that the [Lexer] produces from its parsing is supplied
-by the client; this is normally a simple enumeration.
+by the client; this is normally a simple enumeration. The token type
+need not be [Copy], so tokens may own values such as the decoded
+contents of string literals or interned symbols; or, with
+[LexerOfStr::get_str], tokens may borrow their text from the [str]
+being lexed.
+
+Identifiers may be interned as small [Copy] [Symbol]s in an
+[Interner]; an [Interner] given to a [LexerOfStr] is available to its
+parse functions through [CharStream::interner].
 
 The parsing is managed by the [Lexer] with the client providing a
 slice of matching functions; each matching function is applied in
@@ -95,6 +103,36 @@ token and advances the parsing state. The parsers can generate an
 error if they detect a real error in the stream (not just a mismatch
 to their token type).
 
+The [parsers] module provides builders for commonly required
+matching functions - for whitespace, identifiers, integers, floats,
+string and character literals, comments, and single-character
+punctuation.
+
+For a language with many tokens, a [DfaBuilder] compiles literal and
+pattern rules (with priorities) into a single [Dfa] that matches the
+longest token in one pass over the text; the [Dfa] provides a parse
+function for any [Lexer], or a [DfaLexer] uses it directly to lex a
+[str].
+
+With the `derive` feature, `#[derive(Token)]` generates the parse
+functions (using a [Dfa]) and `Display` for an enumeration of tokens
+whose variants are annotated with `#[token("..")]`, `#[regex("..")]`
+and `#[skip]`; see the `lexer-rs-derive` crate for details.
+
+Where the tokens depend on context (such as string interpolation or
+template languages), a [ModalLexer] has a stack of modes each with
+its own parse functions; its state is a [ModalPosn], which remains
+[Copy] so that it may be saved and restored.
+
+For indentation-sensitive languages, [Lexer::iter_indented] provides
+an [IndentIterator] that adds newline, indent and dedent tokens to
+those of any [Lexer], ignoring blank and comment-only lines.
+
+For formatters and refactoring tools, a [TriviaLexer] attaches the
+whitespace and comments of any [Lexer] as leading and trailing trivia
+to the significant tokens, so that the text can be reproduced exactly
+from the [TriviaTokens].
+
 # Error reporting
 
 With the file position handling used within the [Lexer] it is possible
@@ -103,25 +141,89 @@ retained by the [Lexer] then an error can be displayed with the text
 from the source with the error point/region highlighted.
 
 Support for this is provided by the [FmtContext] trait, which is
-implemented particularly for [LexerOfString].
+implemented particularly for [LexerOfString]. The format of the
+context may be configured with a [ContextStyle].
+
+For an editor, the text of a [LexerOfString] may be changed in place
+with [LexerOfString::edit], and its tokens updated with
+[LexerOfString::relex], which re-lexes only as far as is needed for
+the token stream to re-synchronise with that before the edit.
+
+When the text comes from many files, a [SourceMap] holds each file
+with a distinct [FileId]; the [FilePosn] position type carries the
+[FileId] so that tokens and errors identify their file, and context
+is displayed with a `path:line:col` header.
+
+For compiler-style messages a [Diagnostic] provides a severity, a
+message, a primary span, secondary labelled spans, notes and help; it
+is rendered using any [FmtContext], and may be created from a
+[SimpleParseError].
+
+# Parsing tokens
+
+The tokens produced by a [Lexer] will generally be parsed further
+according to the grammar of a language. The [parser_fn] module
+provides combinators for building such a grammar from parser
+functions; these operate on any stream of tokens that implements
+[ParserInputStream], and they return a [ParseFnResult] indicating if
+the parser function matched the tokens at the start of the stream.
+
+A [LexerTokenStream] provides such a stream of tokens directly from
+any [Lexer] and its token parser functions, optionally skipping
+tokens (such as whitespace) that the grammar does not require.
+
+For hand-written recursive-descent parsers a [TokenCursor] buffers the
+tokens from a [Lexer] with their spans, providing lookahead of any
+number of tokens, mark and reset for backtracking, and 'expect'
+methods that return a [TokenCursorError] with the span of an
+unexpected token.
 
 !*/
 
 //a Imports
 mod char_stream;
+mod dfa;
+mod diagnostic;
+mod interner;
 mod lexer;
+mod parser;
+pub mod parser_fn;
+pub mod parsers;
 mod posn_and_span;
+mod source_map;
 
 pub use char_stream::CharStream;
+pub use char_stream::ContextStyle;
 pub use char_stream::FmtContext;
+pub use diagnostic::{Diagnostic, Severity};
+pub use interner::{Interner, Symbol};
+
+pub use dfa::{Dfa, DfaBuilder, DfaError, DfaLexer};
+
+#[cfg(feature = "derive")]
+pub use lexer_rs_derive::Token;
 
 pub use posn_and_span::LineColumn;
+pub use posn_and_span::ModalPosn;
 pub use posn_and_span::StreamCharPos;
 pub use posn_and_span::StreamCharSpan;
+pub use posn_and_span::{FileId, FilePosn};
 pub use posn_and_span::{PosnInCharStream, UserPosn};
 
 pub use crate::lexer::LexerOfStr;
-pub use crate::lexer::LexerOfString;
-pub use crate::lexer::ParserIterator;
-pub use crate::lexer::SimpleParseError;
-pub use crate::lexer::{BoxDynLexerParseFn, Lexer, LexerError, LexerParseFn, LexerParseResult};
+pub use crate::lexer::ModalLexer;
+pub use crate::lexer::{BoxDynLexerParseFn, BoxDynLexerRecoveryFn, LexerRecovery};
+pub use crate::lexer::{CompactingIterator, LexerOfReader};
+pub use crate::lexer::{IndentIterator, IndentToken};
+pub use crate::lexer::{Lexer, LexerError, LexerParseFn, LexerParseResult};
+pub use crate::lexer::{LexerOfString, TextEdit};
+pub use crate::lexer::{ParserIterator, SpannedParserIterator};
+pub use crate::lexer::{SimpleParseError, SimpleParseErrorKind};
+pub use crate::lexer::{TriviaLexer, TriviaToken, TriviaTokens};
+
+pub use source_map::{SourceFile, SourceMap};
+
+pub use parser::LexerTokenStream;
+pub use parser::{ParseFnResult, ParseResult};
+pub use parser::{ParserInput, ParserInputResult, ParserInputStream};
+pub use parser::{TokenCursor, TokenCursorError};
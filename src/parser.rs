@@ -1,4 +1,8 @@
 mod input;
+mod lexer_token_stream;
+mod token_cursor;
 mod traits;
 pub use input::{ParserInput, ParserInputResult, ParserInputStream};
+pub use lexer_token_stream::LexerTokenStream;
+pub use token_cursor::{TokenCursor, TokenCursorError};
 pub use traits::{ParseFnResult, ParseResult};
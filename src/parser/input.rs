@@ -23,16 +23,17 @@ pub trait ParserInput: Sized {
     /// An error type that the parser returns if there is a failure to
     /// parse a input stream of tokens
     ///
-    /// Using the lexer the Error type will often be:
+    /// Using a [crate::Lexer] to provide the tokens the Error type
+    /// will often be the error type of the lexer, i.e.:
     ///
-    ///    Error : lexer::TokenTypeError<Pos : lexer::TextPos>
+    /// ```ignore
+    ///    Error : lexer_rs::LexerError<Pos : lexer_rs::UserPosn>
+    /// ```
     ///
     /// Pos is the type of a position in an input that needs to be
     /// reported for errors, or that is traced within the Tokens;
     /// often this is the file and start/end lines/characters of the
     /// token
-    ///
-    ///
     type Error;
 
     /// The input type that provides the 'get_token' function to
@@ -44,10 +45,12 @@ pub trait ParserInput: Sized {
 }
 
 //tp ParserInputResult
-/// This is the result of the Parser::Stream::get_token function,
-/// which takes
+/// This is the result of the ParserInput::Stream::get_token function
+///
+/// At the end of the input stream this is Ok(None); otherwise it is
+/// the input stream beyond the next token, and that token.
 ///
-/// P:Parser
+/// P:ParserInput
 pub type ParserInputResult<P> = Result<
     Option<(<P as ParserInput>::Stream, <P as ParserInput>::Token)>,
     <P as ParserInput>::Error,
@@ -64,5 +67,8 @@ pub type ParserInputResult<P> = Result<
 /// Requiring Copy here allows parser functions to manipulate the
 /// input simply without explicit cloning
 pub trait ParserInputStream<P: ParserInput<Stream = Self>>: Copy {
+    /// Get the next token from the input stream, returning the
+    /// stream state after the token with the token itself, or None if
+    /// the stream is at its end
     fn get_token(&self) -> ParserInputResult<P>;
 }
@@ -0,0 +1,143 @@
+//a Imports
+use crate::{BoxDynLexerParseFn, Lexer};
+use crate::{ParserInput, ParserInputResult, ParserInputStream};
+
+//a LexerTokenStream
+//ti TriviaFn
+/// The type of the predicate used to determine if a token is trivia
+type TriviaFn<'a, L> = &'a dyn Fn(&<L as Lexer>::Token) -> bool;
+
+//tp LexerTokenStream
+/// A [ParserInputStream] of the tokens parsed from a [Lexer] using a
+/// slice of parser functions
+///
+/// This provides the bridge from a [Lexer] (such as a
+/// [crate::LexerOfStr]) to the parser functions in
+/// [crate::parser_fn]; it is both the [ParserInput] and the
+/// [ParserInputStream] for those parser functions.
+///
+/// The stream is just a reference to the [Lexer], the [Lexer] state
+/// and a reference to the token parser functions; hence it is [Copy],
+/// and parser functions may backtrack by retaining copies of the
+/// stream. Each invocation of 'get_token' parses the next token from
+/// the [Lexer] state, so backtracking causes tokens to be reparsed.
+///
+/// Optionally the stream can skip 'trivia' tokens (such as whitespace
+/// and comments) that are of no interest to the grammar, using a
+/// predicate supplied to [LexerTokenStream::skip_trivia]
+pub struct LexerTokenStream<'a, L>
+where
+    L: Lexer,
+{
+    lexer: &'a L,
+    state: L::State,
+    parsers: &'a [BoxDynLexerParseFn<'a, L>],
+    is_trivia: Option<TriviaFn<'a, L>>,
+}
+
+//ip Copy for LexerTokenStream
+impl<'a, L> Copy for LexerTokenStream<'a, L> where L: Lexer {}
+
+//ip Clone for LexerTokenStream
+impl<'a, L> Clone for LexerTokenStream<'a, L>
+where
+    L: Lexer,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+//ip Debug for LexerTokenStream
+impl<'a, L> std::fmt::Debug for LexerTokenStream<'a, L>
+where
+    L: Lexer,
+{
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        fmt.debug_struct("LexerTokenStream")
+            .field("lexer", self.lexer)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+//ip LexerTokenStream
+impl<'a, L> LexerTokenStream<'a, L>
+where
+    L: Lexer,
+{
+    //fp new
+    /// Create a new [LexerTokenStream] at the start of a [Lexer]
+    /// stream, using the given token parser functions
+    pub fn new(lexer: &'a L, parsers: &'a [BoxDynLexerParseFn<'a, L>]) -> Self {
+        Self::at_state(lexer, lexer.start(), parsers)
+    }
+
+    //fp at_state
+    /// Create a new [LexerTokenStream] at a particular state of a
+    /// [Lexer] stream, using the given token parser functions
+    pub fn at_state(
+        lexer: &'a L,
+        state: L::State,
+        parsers: &'a [BoxDynLexerParseFn<'a, L>],
+    ) -> Self {
+        Self {
+            lexer,
+            state,
+            parsers,
+            is_trivia: None,
+        }
+    }
+
+    //cp skip_trivia
+    /// Skip any tokens for which 'is_trivia' returns true, so that
+    /// they are never presented to the parser functions
+    pub fn skip_trivia(mut self, is_trivia: &'a dyn Fn(&L::Token) -> bool) -> Self {
+        self.is_trivia = Some(is_trivia);
+        self
+    }
+
+    //ap lexer
+    /// Get the [Lexer] that the stream is parsing
+    pub fn lexer(&self) -> &'a L {
+        self.lexer
+    }
+
+    //ap state
+    /// Get the state of the [Lexer] stream; this is the state after
+    /// the last token that was parsed to get to this stream, and
+    /// hence it may precede trivia that will be skipped
+    pub fn state(&self) -> L::State {
+        self.state
+    }
+}
+
+//ip ParserInput for LexerTokenStream
+impl<'a, L> ParserInput for LexerTokenStream<'a, L>
+where
+    L: Lexer,
+{
+    type Token = L::Token;
+    type Error = L::Error;
+    type Stream = Self;
+}
+
+//ip ParserInputStream for LexerTokenStream
+impl<'a, L> ParserInputStream<LexerTokenStream<'a, L>> for LexerTokenStream<'a, L>
+where
+    L: Lexer,
+{
+    fn get_token(&self) -> ParserInputResult<Self> {
+        let mut state = self.state;
+        while let Some((next_state, token)) = self.lexer.parse(state, self.parsers)? {
+            state = next_state;
+            if let Some(is_trivia) = self.is_trivia {
+                if is_trivia(&token) {
+                    continue;
+                }
+            }
+            return Ok(Some((Self { state, ..*self }, token)));
+        }
+        Ok(None)
+    }
+}
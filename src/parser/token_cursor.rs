@@ -0,0 +1,308 @@
+//a Imports
+use std::collections::VecDeque;
+
+use crate::{BoxDynLexerParseFn, Lexer, PosnInCharStream, StreamCharSpan};
+
+//a TokenCursorError
+//tp TokenCursorError
+/// An error returned by the 'expect' methods of a [TokenCursor]
+///
+/// The error is either an error from the [Lexer], or an unexpected
+/// token with its span, or the unexpected end of the stream (at a
+/// position)
+#[derive(Debug, Clone)]
+pub enum TokenCursorError<P, T, E>
+where
+    P: PosnInCharStream,
+{
+    /// An error from the lexer
+    Lexer(E),
+    /// A token that was not that expected, with its span
+    Unexpected(StreamCharSpan<P>, T),
+    /// The end of the stream, where a token was expected
+    EndOfStream(P),
+}
+
+//ip TokenCursorError
+impl<P, T, E> TokenCursorError<P, T, E>
+where
+    P: PosnInCharStream,
+{
+    //ap span
+    /// Get the span of the unexpected token (or an empty span at the
+    /// end of the stream), if the error is not from the lexer
+    pub fn span(&self) -> Option<StreamCharSpan<P>> {
+        match self {
+            Self::Lexer(_) => None,
+            Self::Unexpected(span, _) => Some(*span),
+            Self::EndOfStream(p) => Some(StreamCharSpan::new(*p, *p)),
+        }
+    }
+}
+
+//ip From<E> for TokenCursorError
+impl<P, T, E> From<E> for TokenCursorError<P, T, E>
+where
+    P: PosnInCharStream,
+{
+    fn from(e: E) -> Self {
+        Self::Lexer(e)
+    }
+}
+
+//ip Display for TokenCursorError
+impl<P, T, E> std::fmt::Display for TokenCursorError<P, T, E>
+where
+    P: PosnInCharStream,
+    T: std::fmt::Debug,
+    E: std::fmt::Display,
+{
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            Self::Lexer(e) => e.fmt(fmt),
+            Self::Unexpected(span, token) => {
+                write!(fmt, "Unexpected token {:?} at ", token)?;
+                span.start().error_fmt(fmt)
+            }
+            Self::EndOfStream(p) => {
+                write!(fmt, "Unexpected end of stream at ")?;
+                p.error_fmt(fmt)
+            }
+        }
+    }
+}
+
+//ip Error for TokenCursorError
+impl<P, T, E> std::error::Error for TokenCursorError<P, T, E>
+where
+    P: PosnInCharStream,
+    T: std::fmt::Debug,
+    E: std::error::Error,
+{
+}
+
+//a TokenCursor
+//ti TriviaFn
+/// The type of the predicate used to determine if a token is trivia
+type TriviaFn<'a, L> = &'a dyn Fn(&<L as Lexer>::Token) -> bool;
+
+//ti SpannedToken
+/// A token with the span of the stream it was parsed from
+type SpannedToken<L> = (StreamCharSpan<<L as Lexer>::State>, <L as Lexer>::Token);
+
+//ti CursorResult
+/// The result of the 'expect' methods of a [TokenCursor]
+type CursorResult<L, V> =
+    Result<V, TokenCursorError<<L as Lexer>::State, <L as Lexer>::Token, <L as Lexer>::Error>>;
+
+//tp TokenCursor
+/// A cursor over the tokens parsed from a [Lexer] using a slice of
+/// parser functions, for hand-written recursive-descent parsers
+///
+/// The cursor buffers tokens (with their spans) as they are parsed,
+/// so that any number of tokens ahead may be examined with
+/// [TokenCursor::peek] and [TokenCursor::peek_nth] before they are
+/// consumed with [TokenCursor::next_token]. The 'expect' methods
+/// consume a token if it is the one required, and otherwise return a
+/// [TokenCursorError] carrying the span of the unexpected token.
+///
+/// The state of the cursor may be saved with [TokenCursor::mark] and
+/// restored with [TokenCursor::reset], for backtracking; the buffer
+/// is discarded on a reset, and tokens are parsed again as required.
+///
+/// Errors from the lexer are returned by the method that required
+/// the token; the cursor does not advance past an error, so the
+/// error is returned again if the token is required again.
+///
+/// Optionally the cursor can skip 'trivia' tokens (such as whitespace
+/// and comments), using a predicate supplied to
+/// [TokenCursor::skip_trivia]
+pub struct TokenCursor<'a, L>
+where
+    L: Lexer,
+    L::State: PosnInCharStream,
+{
+    lexer: &'a L,
+    parsers: &'a [BoxDynLexerParseFn<'a, L>],
+    is_trivia: Option<TriviaFn<'a, L>>,
+    state: L::State,
+    end: L::State,
+    buffer: VecDeque<SpannedToken<L>>,
+}
+
+//ip Debug for TokenCursor
+impl<'a, L> std::fmt::Debug for TokenCursor<'a, L>
+where
+    L: Lexer,
+    L::State: PosnInCharStream,
+{
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        fmt.debug_struct("TokenCursor")
+            .field("lexer", self.lexer)
+            .field("state", &self.state)
+            .field("buffer", &self.buffer)
+            .finish()
+    }
+}
+
+//ip TokenCursor
+impl<'a, L> TokenCursor<'a, L>
+where
+    L: Lexer,
+    L::State: PosnInCharStream,
+{
+    //fp new
+    /// Create a new [TokenCursor] at the start of a [Lexer] stream,
+    /// using the given token parser functions
+    pub fn new(lexer: &'a L, parsers: &'a [BoxDynLexerParseFn<'a, L>]) -> Self {
+        Self::at_state(lexer, lexer.start(), parsers)
+    }
+
+    //fp at_state
+    /// Create a new [TokenCursor] at a particular state of a [Lexer]
+    /// stream, using the given token parser functions
+    pub fn at_state(
+        lexer: &'a L,
+        state: L::State,
+        parsers: &'a [BoxDynLexerParseFn<'a, L>],
+    ) -> Self {
+        Self {
+            lexer,
+            parsers,
+            is_trivia: None,
+            state,
+            end: state,
+            buffer: VecDeque::new(),
+        }
+    }
+
+    //cp skip_trivia
+    /// Skip any tokens for which 'is_trivia' returns true, so that
+    /// they are never returned by the cursor
+    pub fn skip_trivia(mut self, is_trivia: &'a dyn Fn(&L::Token) -> bool) -> Self {
+        self.is_trivia = Some(is_trivia);
+        self
+    }
+
+    //mp mark
+    /// Get the state of the cursor, for a later [TokenCursor::reset];
+    /// this is the state after the last token consumed, and hence it
+    /// may precede trivia that will be skipped
+    pub fn mark(&self) -> L::State {
+        self.state
+    }
+
+    //mp reset
+    /// Reset the cursor to a state (such as one returned by
+    /// [TokenCursor::mark]), discarding any buffered tokens
+    pub fn reset(&mut self, state: L::State) {
+        self.state = state;
+        self.end = state;
+        self.buffer.clear();
+    }
+
+    //mi fill
+    /// Parse tokens into the buffer until it holds 'n' tokens or the
+    /// end of the stream is reached
+    fn fill(&mut self, n: usize) -> Result<(), L::Error> {
+        while self.buffer.len() < n {
+            let start = self.end;
+            match self.lexer.parse(start, self.parsers)? {
+                None => break,
+                Some((end, token)) => {
+                    self.end = end;
+                    if !self.is_trivia.map_or(false, |f| f(&token)) {
+                        self.buffer
+                            .push_back((StreamCharSpan::new(start, end), token));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    //mp peek_nth
+    /// Get the token (and its span) 'k' tokens ahead of the cursor
+    /// (0 being the next token), or None if the stream ends before
+    /// it
+    pub fn peek_nth(&mut self, k: usize) -> Result<Option<&SpannedToken<L>>, L::Error> {
+        self.fill(k + 1)?;
+        Ok(self.buffer.get(k))
+    }
+
+    //mp peek
+    /// Get the next token (and its span), or None at the end of the
+    /// stream
+    pub fn peek(&mut self) -> Result<Option<&SpannedToken<L>>, L::Error> {
+        self.peek_nth(0)
+    }
+
+    //mp is_at_end
+    /// Return true if there are no more tokens in the stream
+    pub fn is_at_end(&mut self) -> Result<bool, L::Error> {
+        Ok(self.peek()?.is_none())
+    }
+
+    //mp next_token
+    /// Consume the next token, returning it with its span, or None at
+    /// the end of the stream
+    pub fn next_token(&mut self) -> Result<Option<SpannedToken<L>>, L::Error> {
+        self.fill(1)?;
+        let next = self.buffer.pop_front();
+        if let Some((span, _)) = &next {
+            self.state = *span.end();
+        }
+        Ok(next)
+    }
+
+    //mp eat
+    /// Consume the next token if it is equal to 'token', returning
+    /// its span if so
+    pub fn eat(&mut self, token: &L::Token) -> Result<Option<StreamCharSpan<L::State>>, L::Error>
+    where
+        L::Token: PartialEq,
+    {
+        match self.peek()? {
+            Some((span, t)) if t == token => {
+                let span = *span;
+                self.next_token()?;
+                Ok(Some(span))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    //mp expect_map
+    /// Consume the next token if 'f' maps it to a value, returning
+    /// its span and the value; otherwise return an error with the
+    /// unexpected token (or the end of the stream)
+    pub fn expect_map<V, F>(&mut self, f: F) -> CursorResult<L, (StreamCharSpan<L::State>, V)>
+    where
+        L::Token: Clone,
+        F: FnOnce(&L::Token) -> Option<V>,
+    {
+        match self.peek()? {
+            None => Err(TokenCursorError::EndOfStream(self.end)),
+            Some((span, token)) => match f(token) {
+                Some(value) => {
+                    let span = *span;
+                    self.next_token()?;
+                    Ok((span, value))
+                }
+                None => Err(TokenCursorError::Unexpected(*span, token.clone())),
+            },
+        }
+    }
+
+    //mp expect
+    /// Consume the next token if it is equal to 'token', returning its
+    /// span; otherwise return an error with the unexpected token (or
+    /// the end of the stream)
+    pub fn expect(&mut self, token: &L::Token) -> CursorResult<L, StreamCharSpan<L::State>>
+    where
+        L::Token: Clone + PartialEq,
+    {
+        self.expect_map(|t| (t == token).then_some(()))
+            .map(|(span, _)| span)
+    }
+}
@@ -3,12 +3,31 @@ use crate::ParserInput;
 
 //a ParseResult, ParseFnResult
 //tp ParseResult
+/// The result of a successful invocation of a parser function
+///
+/// A parser function either matches its input, in which case it
+/// returns the input stream beyond the tokens that it has consumed
+/// together with its result, or it mismatches - in which case the
+/// input stream is not advanced, and another parser function may be
+/// tried on the same input.
+///
+/// P : ParserInput
 #[derive(Debug)]
 pub enum ParseResult<P: ParserInput, R> {
+    /// The parser function did not match the input stream
     Mismatched,
+    /// The parser function matched, and the input stream after the
+    /// match is returned with the result of the parser function
     Matched(P::Stream, R),
 }
 
-//tp ParserFnResult
-// P:ParserInput<'a, Error = E>
+//tp ParseFnResult
+/// The return type of a parser function
+///
+/// An Err is returned if the parser function hits a real error
+/// (including an error from the lexer supplying the tokens), and the
+/// parsing should be abandoned; otherwise the [ParseResult] indicates
+/// if the parser function matched
+///
+/// P : ParserInput
 pub type ParseFnResult<P, R> = Result<ParseResult<P, R>, <P as ParserInput>::Error>;
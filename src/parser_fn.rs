@@ -1,3 +1,25 @@
+//a Documentation
+/*!
+
+# Parser functions
+
+This module provides parser combinators - functions that take parser
+functions and return new parser functions - for parsing a stream of
+tokens that implements [crate::ParserInputStream].
+
+A parser function is any `Fn(I) -> ParseFnResult<P, R>` where `I` is
+the [crate::ParserInputStream] type for the [crate::ParserInput] `P`;
+it either matches tokens at the start of the stream, returning the
+stream after them and a result of type `R`, or it mismatches.
+
+The combinators in this module allow a grammar to be built up from
+parser functions that match individual tokens (such as [token_map]
+and [token_matches]) into sequences ([pair], [tuple3], [delimited],
+and so on), alternatives ([first_of_2], [first_of_n_dyn_ref], and so
+on) and repetitions ([list_of], [fold], [count_of]).
+
+!*/
+
 //a Imports
 #[macro_use]
 mod macros;
@@ -119,7 +119,7 @@ many_f_many_r! {
     /// 'sep', 'b' and which returns a pair of the parsed matched
     /// content for 'a' and 'b' if all three match.
     ///
-    /// An example use would be to parse <name> ';' <type>
+    /// An example use would be to parse `<name> ';' <type>`
     separated_pair, ( f1: F1 : R1, f2 : F2 : R2, f3 : F3 : R3), (R1, R3), stream {
         use ParseResult::*;
         let (stream, r1) = {
@@ -156,7 +156,7 @@ many_f_many_r! {
     /// 'content', 'post' and which returns the parsed matched content
     /// if all three match.
     ///
-    /// An example use would be to parse '(' <expr>> ')'
+    /// An example use would be to parse `'(' <expr> ')'`
     delimited, ( f1: F1 : R1, f2 : F2 : R2, f3 : F3 : R3), R2, stream {
         use ParseResult::*;
         let (stream, _r1) = {
@@ -222,7 +222,7 @@ many_f_many_r! {
     /// and 'post' and which returns the parsed matched content
     /// if both match.
     ///
-    /// An example use would be to parse <statement> ';'
+    /// An example use would be to parse `<statement> ';'`
     succeeded, ( f1: F1 : R1, f2 : F2 : R2), R1, stream {
         use ParseResult::*;
         let (stream, r1) = {
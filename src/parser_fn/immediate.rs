@@ -3,6 +3,8 @@ use crate::{ParseFnResult, ParseResult, ParserInput, ParserInputStream};
 
 //a Success, fail and error
 //fp success
+/// Generate a parser function that always matches without consuming
+/// any tokens, with the result of invoking the provided function
 pub fn success<P, I: ParserInputStream<P>, F, R>(result: F) -> impl Fn(I) -> ParseFnResult<P, R>
 where
     P: ParserInput<Stream = I>,
@@ -13,6 +15,7 @@ where
 }
 
 //fp fail
+/// Generate a parser function that never matches
 pub fn fail<P, I: ParserInputStream<P>, R>() -> impl Fn(I) -> ParseFnResult<P, R>
 where
     P: ParserInput<Stream = I>,
@@ -22,6 +25,8 @@ where
 }
 
 //fp error
+/// Generate a parser function that always returns the error
+/// generated by the provided function
 pub fn error<P, I: ParserInputStream<P>, R, E>(e: E) -> impl Fn(I) -> ParseFnResult<P, R>
 where
     P: ParserInput<Stream = I>,
@@ -9,7 +9,8 @@
 //   *  <fn>_dyn_ref_else([&dyn Fn() -> ParseFnResult<R>], Fn()-> Error) -> impl ParserFn<P, R>
 //
 macro_rules! one_f_one_r_slice {
-    ( $fn_name:ident,
+    ( $(#[$outer:meta])*
+      $fn_name:ident,
       $fs:ident,
       $stream : ident
       { $($content:tt)* }
@@ -17,6 +18,10 @@ macro_rules! one_f_one_r_slice {
 
         paste::paste! {
 
+$(#[$outer])*
+///
+/// The functions are borrowed as dyn Fn, so the returned parser
+/// function has a lifetime 'b that matches that
 pub fn [<$fn_name _dyn_ref>] <'b, P, I: ParserInputStream<P>, R, const N : usize>(
     $fs: [ &'b (dyn Fn(I) -> ParseFnResult<P, R> +'b) ; N]
     ) -> impl Fn(I) -> ParseFnResult<P, R> + 'b
@@ -26,6 +31,12 @@ pub fn [<$fn_name _dyn_ref>] <'b, P, I: ParserInputStream<P>, R, const N : usize
     move |$stream| { $($content)* }
 } // pub fn
 
+$(#[$outer])*
+///
+/// The functions are borrowed as dyn Fn, so the returned parser
+/// function has a lifetime 'b that matches that; if the parser
+/// function would mismatch then the error generated by 'g' is
+/// returned instead
 pub fn [<$fn_name _dyn_ref_else>] <'b, P, I: ParserInputStream<P>, R, G, const N : usize>(
     $fs: [ &'b (dyn Fn(I) -> ParseFnResult<P, R> +'b) ; N],
     g : G,
@@ -59,7 +70,8 @@ pub fn [<$fn_name _dyn_ref_else>] <'b, P, I: ParserInputStream<P>, R, G, const N
 //   *  <fn>_dyn_ref_else(f1:&dyn F1, f2:&dyn F2, ..., Fn()-> Error) -> impl ParserFn<P, R>
 //
 macro_rules! many_f_one_r {
-    ( $fn_name:ident,
+    ( $(#[$outer:meta])*
+      $fn_name:ident,
       ( $($f:ident : $ft:ident  , )+  $(,)? )
       $stream : ident
       { $($content:tt)* }
@@ -67,6 +79,9 @@ macro_rules! many_f_one_r {
 
         paste::paste! {
 
+$(#[$outer])*
+///
+/// The provided parser functions are consumed into a closure
 pub fn $fn_name<P, I: ParserInputStream<P>, R, $($ft, )*>(
     $( $f : $ft , )*
     ) -> impl Fn(I) -> ParseFnResult<P, R>
@@ -77,6 +92,11 @@ pub fn $fn_name<P, I: ParserInputStream<P>, R, $($ft, )*>(
     move |$stream| { $($content)* }
 } // pub fn
 
+$(#[$outer])*
+///
+/// The provided parser functions are consumed into a closure; if the
+/// parser function would mismatch then the error generated by 'g' is
+/// returned instead
 pub fn [< $fn_name _else >] <P, I: ParserInputStream<P>, R, $($ft, )* G>(
     $( $f : $ft , )*
     g : G,
@@ -95,6 +115,10 @@ pub fn [< $fn_name _else >] <P, I: ParserInputStream<P>, R, $($ft, )* G>(
         }
 } // pub fn
 
+$(#[$outer])*
+///
+/// The functions are borrowed, so the returned parser function has a
+/// lifetime 'b that matches that
 pub fn [< $fn_name _ref>] <'b, P, I: ParserInputStream<P>, R, $($ft, )*>(
     $( $f : &'b $ft , )*
     ) -> impl Fn(I) -> ParseFnResult<P, R> + 'b
@@ -105,6 +129,11 @@ pub fn [< $fn_name _ref>] <'b, P, I: ParserInputStream<P>, R, $($ft, )*>(
     move |$stream| { $($content)* }
 } // pub fn
 
+$(#[$outer])*
+///
+/// The functions are borrowed, so the returned parser function has a
+/// lifetime 'b that matches that; if the parser function would
+/// mismatch then the error generated by 'g' is returned instead
 pub fn [< $fn_name _ref_else>] <'b, P, I: ParserInputStream<P>, R, $($ft, )* G>(
     $( $f : &'b $ft , )*
     g : G,
@@ -123,6 +152,10 @@ pub fn [< $fn_name _ref_else>] <'b, P, I: ParserInputStream<P>, R, $($ft, )* G>(
         }
 } // pub fn
 
+$(#[$outer])*
+///
+/// The functions are borrowed as dyn Fn, so the returned parser
+/// function has a lifetime 'b that matches that
 pub fn [< $fn_name _dyn_ref>] <'b, P, I: ParserInputStream<P>, R>(
     $( $f : &'b (dyn Fn(I) -> ParseFnResult<P, R> +'b) , )*
     ) -> impl Fn(I) -> ParseFnResult<P, R> + 'b
@@ -132,6 +165,12 @@ pub fn [< $fn_name _dyn_ref>] <'b, P, I: ParserInputStream<P>, R>(
     move |$stream| { $($content)* }
 } // pub fn
 
+$(#[$outer])*
+///
+/// The functions are borrowed as dyn Fn, so the returned parser
+/// function has a lifetime 'b that matches that; if the parser
+/// function would mismatch then the error generated by 'g' is
+/// returned instead
 pub fn [< $fn_name _dyn_ref_else>] <'b, P, I: ParserInputStream<P>, R, G>(
     $( $f : &'b (dyn Fn(I) -> ParseFnResult<P, R> +'b) , )*
     g: G,
@@ -5,7 +5,8 @@ use crate::{ParseFnResult, ParseResult, ParserInput, ParserInputStream};
 
 //a count
 //fp matches
-///
+/// A parser function generator that matches a single token if the
+/// provided function returns true for it, with a result of ()
 pub fn matches<P, I: ParserInputStream<P>, F>(f: F) -> impl Fn(I) -> ParseFnResult<P, ()>
 where
     P: ParserInput<Stream = I>,
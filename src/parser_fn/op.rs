@@ -3,6 +3,8 @@ use crate::{ParseFnResult, ParseResult, ParserInput, ParserInputStream};
 
 //a map_token
 //fp map
+/// Generate a parser function that applies a parser function 'f' and,
+/// if that matches, maps its result using 'm'
 pub fn map<P, I: ParserInputStream<P>, T, R, F, M>(f: F, m: M) -> impl Fn(I) -> ParseFnResult<P, R>
 where
     P: ParserInput<Stream = I>,
@@ -18,6 +20,12 @@ where
 
 //a Fold
 //fp fold
+/// Generate a parser function that applies a parser function 'f'
+/// and then repeatedly applies 'g' (up to 'max' times, or without
+/// limit if 'max' is zero) while it matches, folding each result of
+/// 'g' into the result using 'fold'
+///
+/// The parser function mismatches only if 'f' mismatches
 pub fn fold<P, I: ParserInputStream<P>, R, F, G, H, T>(
     max: usize,
     fold: H,
@@ -54,6 +62,9 @@ where
 }
 
 //fp option
+/// Generate a parser function that always matches; if 'f' matches
+/// then its result is returned as Some, otherwise the result is None
+/// and no tokens are consumed
 pub fn option<P, I: ParserInputStream<P>, R, F>(f: F) -> impl Fn(I) -> ParseFnResult<P, Option<R>>
 where
     P: ParserInput<Stream = I>,
@@ -67,6 +78,9 @@ where
 }
 
 //fp not
+/// Generate a parser function that matches (without consuming any
+/// tokens) with the result 'r' if 'f' mismatches, and which
+/// mismatches if 'f' matches
 pub fn not<P, I: ParserInputStream<P>, R, T, F>(f: F, r: R) -> impl Fn(I) -> ParseFnResult<P, R>
 where
     P: ParserInput<Stream = I>,
@@ -81,6 +95,8 @@ where
 }
 
 //fp or_else
+/// Generate a parser function that applies 'f', and if that
+/// mismatches then applies 'e' instead
 pub fn or_else<P, I: ParserInputStream<P>, R, F, E>(f: F, e: E) -> impl Fn(I) -> ParseFnResult<P, R>
 where
     P: ParserInput<Stream = I>,
@@ -94,7 +110,10 @@ where
     }
 }
 
-//fp unnwrap_or_else
+//fp unwrap_or_else
+/// Generate a function that applies the parser function 'f', and
+/// returns its result if it matches, or the error generated by 'e'
+/// if it mismatches
 pub fn unwrap_or_else<P, I: ParserInputStream<P>, R, F, E, G>(
     f: F,
     e: G,
@@ -2,16 +2,13 @@
 use crate::{ParseFnResult, ParseResult, ParserInput, ParserInputStream};
 
 //a First-of
-//fp first_of_2/3/4
-// Generate a parser function that attempts up to two parsers; if the
-// first matches then its result is returned, otherwise the second is
-// tried and its result returned. If neither matches then the parser
-// function returns a mismatch.
-//
-// The functions are borrowed, so the returned parser function has a
-// lifetime 'b that matches that; the input (lifetime 'a) must
-// outlive the resultant parser function
-one_f_one_r_slice! { first_of_n, fs, stream {
+//fp first_of_n
+one_f_one_r_slice! {
+    /// Generate a parser function that attempts each of an array of
+    /// parsers in turn; the result of the first that matches is
+    /// returned. If none match then the parser function returns a
+    /// mismatch.
+    first_of_n, fs, stream {
         for f in fs {
             if let ParseResult::Matched(post_token, token) = f(stream)? {
                 return Ok(ParseResult::Matched(post_token, token));
@@ -20,7 +17,12 @@ one_f_one_r_slice! { first_of_n, fs, stream {
         Ok(ParseResult::Mismatched)
 }}
 
-many_f_one_r! { first_of_2, ( f1 : F1, f2 : F2, ) stream {
+//fp first_of_2
+many_f_one_r! {
+    /// Generate a parser function that attempts up to two parsers in
+    /// turn; the result of the first that matches is returned. If
+    /// none match then the parser function returns a mismatch.
+    first_of_2, ( f1 : F1, f2 : F2, ) stream {
 use ParseResult::*;
     if let Matched(post_token, token) = f1(stream)? {
         Ok(Matched(post_token, token))
@@ -30,7 +32,12 @@ use ParseResult::*;
 }
     }
 
-many_f_one_r! { first_of_3, ( f1 : F1, f2 : F2, f3 : F3, ) stream {
+//fp first_of_3
+many_f_one_r! {
+    /// Generate a parser function that attempts up to three parsers in
+    /// turn; the result of the first that matches is returned. If
+    /// none match then the parser function returns a mismatch.
+    first_of_3, ( f1 : F1, f2 : F2, f3 : F3, ) stream {
 use ParseResult::*;
     if let Matched(post_token, token) = f1(stream)? {
         Ok(Matched(post_token, token))
@@ -42,7 +49,12 @@ use ParseResult::*;
 }
     }
 
-many_f_one_r! { first_of_4, ( f1 : F1, f2 : F2, f3 : F3, f4 : F4, ) stream {
+//fp first_of_4
+many_f_one_r! {
+    /// Generate a parser function that attempts up to four parsers in
+    /// turn; the result of the first that matches is returned. If
+    /// none match then the parser function returns a mismatch.
+    first_of_4, ( f1 : F1, f2 : F2, f3 : F3, f4 : F4, ) stream {
 use ParseResult::*;
     if let Matched(post_token, token) = f1(stream)? {
         Ok(Matched(post_token, token))
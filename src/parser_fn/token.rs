@@ -18,13 +18,10 @@ where
 {
     use ParseResult::*;
     move |input| {
-        match input.get_token()? {
-            Some((input, token)) => {
-                if let Some(r) = f(token) {
-                    return Ok(Matched(input, r));
-                }
+        if let Some((input, token)) = input.get_token()? {
+            if let Some(r) = f(token) {
+                return Ok(Matched(input, r));
             }
-            _ => (),
         }
         Ok(Mismatched)
     }
@@ -32,7 +29,8 @@ where
 
 //a Matches
 //fp token_matches
-///
+/// A parser function generator that matches a single token if the
+/// provided function returns true for it, with a result of ()
 pub fn token_matches<P, I: ParserInputStream<P>, F>(f: F) -> impl Fn(I) -> ParseFnResult<P, ()>
 where
     P: ParserInput<Stream = I>,
@@ -0,0 +1,69 @@
+//a Documentation
+/*!
+
+# Token parse functions
+
+This module provides builders for token parse functions that are
+commonly required by a [crate::Lexer], such as for whitespace,
+identifiers, numbers, string and character literals, comments,
+punctuation, and keywords and operators (using a [KeywordTable]).
+
+Each builder returns a [crate::BoxDynLexerParseFn] that may be used
+in the slice of parse functions supplied to the [crate::Lexer]; they
+are generic on any lexer `L` that is a [crate::Lexer] and a
+[crate::CharStream] of its state, and they are parameterised by a
+closure that constructs the token from what has been matched.
+
+```
+use lexer_rs::{parsers, BoxDynLexerParseFn, Lexer, LexerOfStr, SimpleParseError};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Whitespace,
+    Id(usize),
+    Int(u64),
+    Open,
+    Close,
+}
+type TextStream<'a> = LexerOfStr<'a, usize, Token, SimpleParseError<usize>>;
+
+let lexer = TextStream::new("(fred 0x2a)");
+let parsers: [BoxDynLexerParseFn<TextStream>; 4] = [
+    parsers::whitespace(|| Token::Whitespace),
+    parsers::identifier(char::is_alphabetic, char::is_alphanumeric, |s| Token::Id(s.len())),
+    parsers::integer(Token::Int),
+    parsers::char_table(&[('(', Token::Open), (')', Token::Close)]),
+];
+let tokens: Vec<_> = lexer.iter(&parsers).map(|t| t.unwrap()).collect();
+assert_eq!(
+    tokens,
+    [Token::Open, Token::Id(4), Token::Whitespace, Token::Int(42), Token::Close]
+);
+```
+
+With the `regex` feature, a `RegexRule` (for a [crate::LexerOfStr])
+permits tokens to be declared as regular expressions.
+
+The parse functions are tried in order by the [crate::Lexer], so
+where the text matched by one may be a prefix of another (such as a
+float and an integer) the longer should be placed first.
+
+!*/
+
+//a Imports
+mod comment;
+mod keyword;
+mod literal;
+mod number;
+#[cfg(feature = "regex")]
+mod regex_rule;
+mod simple;
+
+//a Exports
+pub use comment::{block_comment, line_comment};
+pub use keyword::KeywordTable;
+pub use literal::{char_literal, raw_string, string};
+pub use number::{float, integer, integer_radix};
+#[cfg(feature = "regex")]
+pub use regex_rule::RegexRule;
+pub use simple::{char_table, identifier, whitespace};
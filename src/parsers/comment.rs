@@ -0,0 +1,108 @@
+//a Imports
+use crate::StreamCharSpan;
+use crate::{BoxDynLexerParseFn, CharStream, Lexer, LexerError, PosnInCharStream};
+
+//a Line comments
+//fp line_comment
+/// Build a parse function that matches a comment that starts with
+/// 'prefix' (such as "//" or "#") and runs to the end of the line
+///
+/// The newline at the end of the comment is not part of the comment.
+/// The prefix must be ASCII, and it must not contain a newline.
+///
+/// The token is constructed by `token` from the span of the body of
+/// the comment (the text after the prefix); for doc-comments (such
+/// as "///") a separate parse function with the longer prefix should
+/// precede that for normal comments.
+///
+/// Panics if 'prefix' is empty
+pub fn line_comment<'a, L, F>(prefix: &'a str, token: F) -> BoxDynLexerParseFn<'a, L>
+where
+    L: Lexer + CharStream<<L as Lexer>::State>,
+    <L as Lexer>::State: PosnInCharStream,
+    F: Fn(StreamCharSpan<<L as Lexer>::State>) -> <L as Lexer>::Token + 'a,
+{
+    assert!(
+        !prefix.is_empty(),
+        "A line comment prefix must not be empty"
+    );
+    Box::new(move |lexer: &L, state, _ch| {
+        if !lexer.matches_str(&state, prefix) {
+            return Ok(None);
+        }
+        let body_start = lexer.consumed_ascii_str(state, prefix);
+        let mut state = body_start;
+        while let Some(c) = lexer.peek_at(&state) {
+            if c == '\n' {
+                break;
+            }
+            state = lexer.consumed_char(state, c);
+        }
+        Ok(Some((state, token(StreamCharSpan::new(body_start, state)))))
+    })
+}
+
+//a Block comments
+//fp block_comment
+/// Build a parse function that matches a comment that starts with
+/// 'open' (such as "/*") and ends with 'close' (such as "*/"),
+/// possibly spanning many lines
+///
+/// If 'nested' is true then comments may be nested, with each 'open'
+/// within the comment requiring a matching 'close'. The delimiters
+/// must be ASCII, and they must not contain newlines.
+///
+/// The token is constructed by `token` from the span of the body of
+/// the comment (the text between the delimiters). An 'unterminated'
+/// error is returned at the position of the opening delimiter if the
+/// comment is not closed before the end of the stream.
+///
+/// Panics if 'open' or 'close' is empty
+pub fn block_comment<'a, L, F>(
+    open: &'a str,
+    close: &'a str,
+    nested: bool,
+    token: F,
+) -> BoxDynLexerParseFn<'a, L>
+where
+    L: Lexer + CharStream<<L as Lexer>::State>,
+    <L as Lexer>::State: PosnInCharStream,
+    F: Fn(StreamCharSpan<<L as Lexer>::State>) -> <L as Lexer>::Token + 'a,
+{
+    assert!(
+        !open.is_empty() && !close.is_empty(),
+        "Block comment delimiters must not be empty"
+    );
+    Box::new(move |lexer: &L, state, ch| {
+        if !lexer.matches_str(&state, open) {
+            return Ok(None);
+        }
+        let start = state;
+        let body_start = lexer.consumed_ascii_str(state, open);
+        let mut state = body_start;
+        let mut depth = 0;
+        loop {
+            if lexer.matches_str(&state, close) {
+                if depth == 0 {
+                    let span = StreamCharSpan::new(body_start, state);
+                    let end = lexer.consumed_ascii_str(state, close);
+                    return Ok(Some((end, token(span))));
+                }
+                depth -= 1;
+                state = lexer.consumed_ascii_str(state, close);
+            } else if nested && lexer.matches_str(&state, open) {
+                depth += 1;
+                state = lexer.consumed_ascii_str(state, open);
+            } else {
+                match lexer.peek_at(&state) {
+                    Some(c) => {
+                        state = lexer.consumed_char(state, c);
+                    }
+                    None => {
+                        return Err(<L as Lexer>::Error::unterminated(start, ch));
+                    }
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,172 @@
+//a Imports
+use crate::{BoxDynLexerParseFn, CharStream, Lexer, PosnInCharStream};
+
+//a Node
+//ti Node
+/// A node in the trie of a [KeywordTable]
+///
+/// The children are sorted by character, and the value (if any) is
+/// the index of the keyword that ends at the node
+#[derive(Debug, Default, Clone)]
+struct Node {
+    children: Vec<(char, usize)>,
+    value: Option<usize>,
+}
+
+//ii Node
+impl Node {
+    //mi child
+    /// Find the index of the child node for a character
+    fn child(&self, ch: char) -> Option<usize> {
+        self.children
+            .binary_search_by_key(&ch, |(c, _)| *c)
+            .ok()
+            .map(|i| self.children[i].1)
+    }
+}
+
+//a KeywordTable
+//tp KeywordTable
+/// A table of keywords (or operators) and their tokens, compiled into
+/// a trie so that the longest keyword matching the stream can be
+/// found in a single pass
+///
+/// For example, with a table containing `=` and `==`, the text `==`
+/// matches the latter; a linear search of the keywords in order
+/// would match `=` if it appeared first.
+///
+/// If a word boundary is required (see
+/// [KeywordTable::with_word_boundary]) then a keyword that ends with
+/// an alphanumeric character (or '_') does not match if the next
+/// character in the stream is also alphanumeric (or '_'); so the
+/// keyword `if` does not match the start of `iffy`, but a shorter
+/// keyword that does end at a boundary may match instead.
+///
+/// The table provides a parse function for a [Lexer] whose token type
+/// is the type of the values in the table, with [KeywordTable::parse_fn]
+#[derive(Debug, Clone)]
+pub struct KeywordTable<T> {
+    nodes: Vec<Node>,
+    values: Vec<T>,
+    word_boundary: bool,
+}
+
+//ip KeywordTable
+impl<T> KeywordTable<T> {
+    //fp new
+    /// Create a new [KeywordTable] from keyword and value pairs
+    ///
+    /// If a keyword appears more than once then the last value is used
+    pub fn new<'k, I>(keywords: I) -> Self
+    where
+        I: IntoIterator<Item = (&'k str, T)>,
+    {
+        let mut table = Self {
+            nodes: vec![Node::default()],
+            values: Vec::new(),
+            word_boundary: false,
+        };
+        for (keyword, value) in keywords {
+            table.add(keyword, value);
+        }
+        table
+    }
+
+    //cp with_word_boundary
+    /// Set whether a word boundary is required after keywords that end
+    /// in an alphanumeric character
+    pub fn with_word_boundary(mut self, word_boundary: bool) -> Self {
+        self.word_boundary = word_boundary;
+        self
+    }
+
+    //mi add
+    /// Add a keyword to the trie
+    fn add(&mut self, keyword: &str, value: T) {
+        let mut node = 0;
+        for ch in keyword.chars() {
+            node = match self.nodes[node]
+                .children
+                .binary_search_by_key(&ch, |(c, _)| *c)
+            {
+                Ok(i) => self.nodes[node].children[i].1,
+                Err(i) => {
+                    let child = self.nodes.len();
+                    self.nodes.push(Node::default());
+                    self.nodes[node].children.insert(i, (ch, child));
+                    child
+                }
+            };
+        }
+        self.values.push(value);
+        self.nodes[node].value = Some(self.values.len() - 1);
+    }
+
+    //ap get
+    /// Get the value of a keyword, if it is in the table
+    ///
+    /// This can be used to map identifiers to keywords
+    pub fn get(&self, keyword: &str) -> Option<&T> {
+        let mut node = 0;
+        for ch in keyword.chars() {
+            node = self.nodes[node].child(ch)?;
+        }
+        self.nodes[node].value.map(|v| &self.values[v])
+    }
+
+    //mi is_word_char
+    /// Return true if the character is part of a word
+    fn is_word_char(ch: char) -> bool {
+        ch.is_alphanumeric() || ch == '_'
+    }
+
+    //mp longest_match
+    /// Find the longest keyword at a state of a stream, returning the
+    /// state after it and its value
+    pub fn longest_match<L, P>(&self, lexer: &L, state: P) -> Option<(P, &T)>
+    where
+        L: CharStream<P>,
+        P: PosnInCharStream,
+    {
+        let mut node = 0;
+        let mut state = state;
+        let mut last_ch = None;
+        let mut result = None;
+        loop {
+            let next_ch = lexer.peek_at(&state);
+            if let Some(v) = self.nodes[node].value {
+                let at_boundary = !self.word_boundary
+                    || !last_ch.map_or(false, Self::is_word_char)
+                    || !next_ch.map_or(false, Self::is_word_char);
+                if at_boundary {
+                    result = Some((state, &self.values[v]));
+                }
+            }
+            match next_ch.and_then(|ch| self.nodes[node].child(ch).map(|c| (ch, c))) {
+                Some((ch, child)) => {
+                    node = child;
+                    state = lexer.consumed_char(state, ch);
+                    last_ch = Some(ch);
+                }
+                None => break,
+            }
+        }
+        result
+    }
+
+    //mp parse_fn
+    /// Create a parse function that matches the longest keyword in the
+    /// table, yielding a clone of its value as the token
+    pub fn parse_fn<'a, L>(&'a self) -> BoxDynLexerParseFn<'a, L>
+    where
+        L: Lexer<Token = T> + CharStream<<L as Lexer>::State>,
+        <L as Lexer>::State: PosnInCharStream,
+        T: Clone,
+    {
+        Box::new(move |lexer: &L, state, _ch| {
+            Ok(self
+                .longest_match(lexer, state)
+                .map(|(end, value)| (end, value.clone())))
+        })
+    }
+}
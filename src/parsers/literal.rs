@@ -0,0 +1,262 @@
+//a Imports
+use std::borrow::Cow;
+
+use crate::{BoxDynLexerParseFn, CharStream, Lexer, LexerError, PosnInCharStream};
+
+//a Helpers
+//fi hex_digits
+/// Step over up to 'max' hexadecimal digits from a state, returning
+/// the state after them, the number of digits and their value
+fn hex_digits<L>(
+    lexer: &L,
+    mut state: <L as Lexer>::State,
+    max: usize,
+) -> (<L as Lexer>::State, usize, u32)
+where
+    L: Lexer + CharStream<<L as Lexer>::State>,
+    <L as Lexer>::State: PosnInCharStream,
+{
+    let mut n = 0;
+    let mut value = 0;
+    while n < max {
+        match lexer
+            .peek_at(&state)
+            .and_then(|ch| ch.to_digit(16).map(|d| (ch, d)))
+        {
+            Some((ch, d)) => {
+                value = value * 16 + d;
+                state = lexer.consumed_char(state, ch);
+                n += 1;
+            }
+            None => break,
+        }
+    }
+    (state, n, value)
+}
+
+//fi escape
+/// Decode an escape sequence whose escape character (backslash) is
+/// at 'state', returning the state after the sequence and the
+/// character it represents
+///
+/// The escapes supported are `\n`, `\r`, `\t`, `\0`, `\\`, `\'`,
+/// `\"`, `\x` followed by two hexadecimal digits of an ASCII
+/// character (at most `7F`), and `\u{...}` with one to six
+/// hexadecimal digits that are a valid [char].
+///
+/// If the stream ends after the escape character then an
+/// 'unterminated' error is returned for the literal that started at
+/// 'start' with 'quote'; otherwise, if the sequence is invalid, a
+/// 'bad_escape' error is returned at the position of the escape
+/// character.
+fn escape<L>(
+    lexer: &L,
+    start: <L as Lexer>::State,
+    quote: char,
+    state: <L as Lexer>::State,
+) -> Result<(<L as Lexer>::State, char), <L as Lexer>::Error>
+where
+    L: Lexer + CharStream<<L as Lexer>::State>,
+    <L as Lexer>::State: PosnInCharStream,
+{
+    let after = lexer.consumed_char(state, '\\');
+    let ch = match lexer.peek_at(&after) {
+        Some(ch) => ch,
+        None => return Err(<L as Lexer>::Error::unterminated(start, quote)),
+    };
+    let bad_escape = || <L as Lexer>::Error::bad_escape(state, ch);
+    let next = lexer.consumed_char(after, ch);
+    match ch {
+        'n' => Ok((next, '\n')),
+        'r' => Ok((next, '\r')),
+        't' => Ok((next, '\t')),
+        '0' => Ok((next, '\0')),
+        '\\' | '\'' | '"' => Ok((next, ch)),
+        'x' => match hex_digits(lexer, next, 2) {
+            (end, 2, value) if value < 0x80 => Ok((end, char::from(value as u8))),
+            _ => Err(bad_escape()),
+        },
+        'u' => {
+            if lexer.peek_at(&next) != Some('{') {
+                return Err(bad_escape());
+            }
+            let (end, n, value) = hex_digits(lexer, lexer.consumed_char(next, '{'), 6);
+            if n == 0 || lexer.peek_at(&end) != Some('}') {
+                return Err(bad_escape());
+            }
+            match char::from_u32(value) {
+                Some(c) => Ok((lexer.consumed_char(end, '}'), c)),
+                None => Err(bad_escape()),
+            }
+        }
+        _ => Err(bad_escape()),
+    }
+}
+
+//a Strings
+//fp string
+/// Build a parse function that matches a string delimited by the
+/// 'quote' character (such as '"' or '\''), decoding any escape
+/// sequences within it
+///
+/// The escapes supported are `\n`, `\r`, `\t`, `\0`, `\\`, `\'`,
+/// `\"`, `\x` followed by two hexadecimal digits of an ASCII
+/// character (at most `7F`), and `\u{...}` with one to six
+/// hexadecimal digits. The string may contain newlines.
+///
+/// The token is constructed by `token` from the decoded contents of
+/// the string; this borrows the text of the stream if there are no
+/// escapes. An 'unterminated' error is returned at the opening quote
+/// if the string is not closed before the end of the stream, and a
+/// 'bad_escape' error is returned at the position of an invalid
+/// escape sequence.
+pub fn string<'a, L, F>(quote: char, token: F) -> BoxDynLexerParseFn<'a, L>
+where
+    L: Lexer + CharStream<<L as Lexer>::State>,
+    <L as Lexer>::State: PosnInCharStream,
+    F: Fn(Cow<str>) -> <L as Lexer>::Token + 'a,
+{
+    Box::new(move |lexer: &L, state, ch| {
+        if ch != quote {
+            return Ok(None);
+        }
+        let start = state;
+        let content_start = lexer.consumed_char(state, ch);
+        let mut state = content_start;
+        let mut decoded: Option<String> = None;
+        loop {
+            match lexer.peek_at(&state) {
+                None => {
+                    return Err(<L as Lexer>::Error::unterminated(start, quote));
+                }
+                Some(c) if c == quote => {
+                    let value = match decoded {
+                        Some(s) => Cow::Owned(s),
+                        None => Cow::Borrowed(lexer.get_text(content_start, state)),
+                    };
+                    return Ok(Some((lexer.consumed_char(state, c), token(value))));
+                }
+                Some('\\') => {
+                    let decoded = decoded
+                        .get_or_insert_with(|| lexer.get_text(content_start, state).to_string());
+                    let (next, c) = escape(lexer, start, quote, state)?;
+                    decoded.push(c);
+                    state = next;
+                }
+                Some(c) => {
+                    if let Some(decoded) = &mut decoded {
+                        decoded.push(c);
+                    }
+                    state = lexer.consumed_char(state, c);
+                }
+            }
+        }
+    })
+}
+
+//fp raw_string
+/// Build a parse function that matches a raw string, such as
+/// `r"..."` or `r#"..."#`, which has no escape sequences
+///
+/// The string starts with 'r', any number of '#' characters and a
+/// '"'; it ends with a '"' followed by the same number of '#'
+/// characters. It may contain newlines.
+///
+/// The token is constructed by `token` from the contents of the
+/// string. An 'unterminated' error is returned at the opening 'r' if
+/// the string is not closed before the end of the stream.
+///
+/// As the text matched starts with 'r', this parse function should
+/// precede one for identifiers.
+pub fn raw_string<'a, L, F>(token: F) -> BoxDynLexerParseFn<'a, L>
+where
+    L: Lexer + CharStream<<L as Lexer>::State>,
+    <L as Lexer>::State: PosnInCharStream,
+    F: Fn(Cow<str>) -> <L as Lexer>::Token + 'a,
+{
+    Box::new(move |lexer: &L, state, ch| {
+        if ch != 'r' {
+            return Ok(None);
+        }
+        let start = state;
+        let mut state = lexer.consumed_char(state, ch);
+        let mut closing = String::from("\"");
+        while lexer.peek_at(&state) == Some('#') {
+            state = lexer.consumed_char(state, '#');
+            closing.push('#');
+        }
+        if lexer.peek_at(&state) != Some('"') {
+            return Ok(None);
+        }
+        let content_start = lexer.consumed_char(state, '"');
+        let mut state = content_start;
+        loop {
+            match lexer.peek_at(&state) {
+                None => {
+                    return Err(<L as Lexer>::Error::unterminated(start, ch));
+                }
+                Some('"') if lexer.matches_str(&state, &closing) => {
+                    let value = Cow::Borrowed(lexer.get_text(content_start, state));
+                    let end = lexer.consumed_ascii_str(state, &closing);
+                    return Ok(Some((end, token(value))));
+                }
+                Some(c) => {
+                    state = lexer.consumed_char(state, c);
+                }
+            }
+        }
+    })
+}
+
+//a Characters
+//fp char_literal
+/// Build a parse function that matches a character literal delimited
+/// by single quotes, decoding an escape sequence (as for [string])
+///
+/// The token is constructed by `token` from the character. If a
+/// single character is not followed by a closing quote then the parse
+/// function does not match, so that a later parse function may match
+/// the quote (such as for a Rust lifetime or label, `'a`).
+///
+/// An 'unterminated' error is returned at the opening quote if it is
+/// at the end of a line or of the stream, or if an escape sequence is
+/// not followed by a closing quote; a 'bad_escape' error is returned
+/// at the position of an invalid escape sequence; and an empty
+/// literal is a 'failed_to_parse' error at its closing quote.
+pub fn char_literal<'a, L, F>(token: F) -> BoxDynLexerParseFn<'a, L>
+where
+    L: Lexer + CharStream<<L as Lexer>::State>,
+    <L as Lexer>::State: PosnInCharStream,
+    F: Fn(char) -> <L as Lexer>::Token + 'a,
+{
+    Box::new(move |lexer: &L, state, ch| {
+        if ch != '\'' {
+            return Ok(None);
+        }
+        let start = state;
+        let state = lexer.consumed_char(state, ch);
+        let (state, c) = match lexer.peek_at(&state) {
+            None | Some('\n') => {
+                return Err(<L as Lexer>::Error::unterminated(start, ch));
+            }
+            Some('\'') => {
+                return Err(<L as Lexer>::Error::failed_to_parse(state, ch));
+            }
+            Some('\\') => {
+                let (state, c) = escape(lexer, start, ch, state)?;
+                if lexer.peek_at(&state) != Some('\'') {
+                    return Err(<L as Lexer>::Error::unterminated(start, ch));
+                }
+                (state, c)
+            }
+            Some(c) => {
+                let state = lexer.consumed_char(state, c);
+                if lexer.peek_at(&state) != Some('\'') {
+                    return Ok(None);
+                }
+                (state, c)
+            }
+        };
+        Ok(Some((lexer.consumed_char(state, ch), token(c))))
+    })
+}
@@ -0,0 +1,156 @@
+//a Imports
+use crate::{BoxDynLexerParseFn, CharStream, Lexer, LexerError, PosnInCharStream};
+
+//a Helpers
+//ti ValueResult
+/// The result of parsing a value at a state of a lexer
+type ValueResult<L, V> = Result<Option<(<L as Lexer>::State, V)>, <L as Lexer>::Error>;
+
+//fi digits
+/// Step over the digits of a radix from a state, returning the state
+/// after them and the number of digits
+fn digits<L>(lexer: &L, mut state: <L as Lexer>::State, radix: u32) -> (<L as Lexer>::State, usize)
+where
+    L: Lexer + CharStream<<L as Lexer>::State>,
+    <L as Lexer>::State: PosnInCharStream,
+{
+    let mut n = 0;
+    while let Some(ch) = lexer.peek_at(&state) {
+        if !ch.is_digit(radix) {
+            break;
+        }
+        state = lexer.consumed_char(state, ch);
+        n += 1;
+    }
+    (state, n)
+}
+
+//fi parse_radix
+/// Parse the digits at a state as an integer of a radix, returning
+/// the state after the digits and the value; an error is returned if
+/// the value does not fit in a u64
+fn parse_radix<L>(
+    lexer: &L,
+    start: <L as Lexer>::State,
+    state: <L as Lexer>::State,
+    ch: char,
+    radix: u32,
+) -> ValueResult<L, u64>
+where
+    L: Lexer + CharStream<<L as Lexer>::State>,
+    <L as Lexer>::State: PosnInCharStream,
+{
+    let (end, n) = digits(lexer, state, radix);
+    if n == 0 {
+        return Ok(None);
+    }
+    match u64::from_str_radix(lexer.get_text(state, end), radix) {
+        Ok(value) => Ok(Some((end, value))),
+        Err(_) => Err(<L as Lexer>::Error::failed_to_parse(start, ch)),
+    }
+}
+
+//a Integers
+//fp integer_radix
+/// Build a parse function that matches an integer of a given radix
+/// with a required prefix (such as "0x" for hexadecimal); the prefix
+/// may be empty
+///
+/// The token is constructed by `token` from the value of the integer;
+/// if the value does not fit in a u64 then an error is returned
+pub fn integer_radix<'a, L, F>(prefix: &'a str, radix: u32, token: F) -> BoxDynLexerParseFn<'a, L>
+where
+    L: Lexer + CharStream<<L as Lexer>::State>,
+    <L as Lexer>::State: PosnInCharStream,
+    F: Fn(u64) -> <L as Lexer>::Token + 'a,
+{
+    Box::new(move |lexer: &L, state, ch| {
+        if !lexer.matches_str(&state, prefix) {
+            return Ok(None);
+        }
+        let digits_start = lexer.consumed_ascii_str(state, prefix);
+        Ok(parse_radix(lexer, state, digits_start, ch, radix)?.map(|(end, v)| (end, token(v))))
+    })
+}
+
+//fp integer
+/// Build a parse function that matches an integer, which may be
+/// hexadecimal (prefixed with "0x"), octal ("0o"), binary ("0b") or
+/// decimal
+///
+/// The token is constructed by `token` from the value of the integer;
+/// if the value does not fit in a u64 then an error is returned
+pub fn integer<'a, L, F>(token: F) -> BoxDynLexerParseFn<'a, L>
+where
+    L: Lexer + CharStream<<L as Lexer>::State>,
+    <L as Lexer>::State: PosnInCharStream,
+    F: Fn(u64) -> <L as Lexer>::Token + 'a,
+{
+    Box::new(move |lexer: &L, state, ch| {
+        if !ch.is_ascii_digit() {
+            return Ok(None);
+        }
+        for (prefix, radix) in [("0x", 16), ("0o", 8), ("0b", 2)] {
+            if lexer.matches_str(&state, prefix) {
+                let digits_start = lexer.consumed_ascii_str(state, prefix);
+                if let Some((end, v)) = parse_radix(lexer, state, digits_start, ch, radix)? {
+                    return Ok(Some((end, token(v))));
+                }
+            }
+        }
+        Ok(parse_radix(lexer, state, state, ch, 10)?.map(|(end, v)| (end, token(v))))
+    })
+}
+
+//a Floats
+//fp float
+/// Build a parse function that matches a floating point number, with
+/// decimal digits, a fractional part and/or an exponent
+///
+/// The number must have at least one digit before the decimal point,
+/// and if there is a decimal point then it must be followed by at
+/// least one digit; the exponent is 'e' or 'E' followed by an
+/// optional sign and at least one digit. A number without a
+/// fractional part or exponent is not matched, so that integers may
+/// be matched by another parse function.
+///
+/// The token is constructed by `token` from the value of the number
+pub fn float<'a, L, F>(token: F) -> BoxDynLexerParseFn<'a, L>
+where
+    L: Lexer + CharStream<<L as Lexer>::State>,
+    <L as Lexer>::State: PosnInCharStream,
+    F: Fn(f64) -> <L as Lexer>::Token + 'a,
+{
+    Box::new(move |lexer: &L, state, ch| {
+        if !ch.is_ascii_digit() {
+            return Ok(None);
+        }
+        let (mut end, _) = digits(lexer, state, 10);
+        let mut is_float = false;
+        if lexer.peek_at(&end) == Some('.') {
+            let (frac_end, n) = digits(lexer, lexer.consumed_char(end, '.'), 10);
+            if n > 0 {
+                end = frac_end;
+                is_float = true;
+            }
+        }
+        if let Some(e) = lexer.peek_at(&end).filter(|c| *c == 'e' || *c == 'E') {
+            let mut exp = lexer.consumed_char(end, e);
+            if let Some(sign) = lexer.peek_at(&exp).filter(|c| *c == '+' || *c == '-') {
+                exp = lexer.consumed_char(exp, sign);
+            }
+            let (exp_end, n) = digits(lexer, exp, 10);
+            if n > 0 {
+                end = exp_end;
+                is_float = true;
+            }
+        }
+        if !is_float {
+            return Ok(None);
+        }
+        match lexer.get_text(state, end).parse() {
+            Ok(value) => Ok(Some((end, token(value)))),
+            Err(_) => Err(<L as Lexer>::Error::failed_to_parse(state, ch)),
+        }
+    })
+}
@@ -0,0 +1,96 @@
+//a Imports
+use regex::{Captures, Regex};
+
+use crate::{BoxDynLexerParseFn, CharStream, LexerError, LexerOfStr, PosnInCharStream};
+
+//a RegexRule
+//tp RegexRule
+/// A token rule given by a regular expression, for a [LexerOfStr]
+///
+/// The regular expression is anchored at the state of the lexer when
+/// the parse function is invoked, so it only matches text starting at
+/// that point; an empty match is not a match. The state after a match
+/// is correctly advanced across any newlines in the matched text.
+///
+/// The parse function maps the captures of the match (including the
+/// whole match as capture group 0) to a token with a closure.
+///
+/// This requires the `regex` feature.
+///
+/// ```
+/// use lexer_rs::{parsers::RegexRule, BoxDynLexerParseFn, Lexer, LexerOfStr, SimpleParseError};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Token {
+///     Whitespace,
+///     Version(u32, u32),
+/// }
+/// type TextStream<'a> = LexerOfStr<'a, usize, Token, SimpleParseError<usize>>;
+///
+/// let version = RegexRule::new(r"v(\d+)\.(\d+)").unwrap();
+/// let whitespace = RegexRule::new(r"\s+").unwrap();
+/// let lexer = TextStream::new("v1.2 v10.0");
+/// let parsers: [BoxDynLexerParseFn<TextStream>; 2] = [
+///     version.parse_fn(|c| Token::Version(c[1].parse().unwrap(), c[2].parse().unwrap())),
+///     whitespace.parse_fn(|_| Token::Whitespace),
+/// ];
+/// let tokens: Vec<_> = lexer.iter(&parsers).map(|t| t.unwrap()).collect();
+/// assert_eq!(
+///     tokens,
+///     [Token::Version(1, 2), Token::Whitespace, Token::Version(10, 0)]
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct RegexRule {
+    regex: Regex,
+}
+
+//ip RegexRule
+impl RegexRule {
+    //fp new
+    /// Create a new [RegexRule] from a regular expression pattern
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        let regex = Regex::new(&format!("^(?:{})", pattern))?;
+        Ok(Self { regex })
+    }
+
+    //ap regex
+    /// Get the (anchored) regular expression of the rule
+    pub fn regex(&self) -> &Regex {
+        &self.regex
+    }
+
+    //mp captures
+    /// Match the rule at the start of some text, returning the
+    /// captures if it matches with a non-empty match
+    pub fn captures<'t>(&self, text: &'t str) -> Option<Captures<'t>> {
+        self.regex
+            .captures(text)
+            .filter(|c| c.get(0).map_or(false, |m| m.start() == 0 && !m.is_empty()))
+    }
+
+    //mp parse_fn
+    /// Create a parse function for a [LexerOfStr] that matches the
+    /// rule, yielding the token returned by `token` for the captures
+    pub fn parse_fn<'a, 's, P, T, E, F>(
+        &'a self,
+        token: F,
+    ) -> BoxDynLexerParseFn<'a, LexerOfStr<'s, P, T, E>>
+    where
+        P: PosnInCharStream,
+        T: Sized + std::fmt::Debug,
+        E: LexerError<P>,
+        F: Fn(&Captures) -> T + 'a,
+    {
+        Box::new(move |lexer: &LexerOfStr<'s, P, T, E>, state, _ch| {
+            match self.captures(lexer.remaining_text(&state)) {
+                Some(captures) => {
+                    let num_chars = captures[0].chars().count();
+                    let end = lexer.consumed(state, num_chars);
+                    Ok(Some((end, token(&captures))))
+                }
+                None => Ok(None),
+            }
+        })
+    }
+}
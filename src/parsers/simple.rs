@@ -0,0 +1,76 @@
+//a Imports
+use crate::{BoxDynLexerParseFn, CharStream, Lexer, PosnInCharStream};
+
+//a Whitespace
+//fp whitespace
+/// Build a parse function that matches a sequence of whitespace
+/// characters (including newlines), yielding the token returned by
+/// `token`
+pub fn whitespace<'a, L, F>(token: F) -> BoxDynLexerParseFn<'a, L>
+where
+    L: Lexer + CharStream<<L as Lexer>::State>,
+    <L as Lexer>::State: PosnInCharStream,
+    F: Fn() -> <L as Lexer>::Token + 'a,
+{
+    Box::new(move |lexer: &L, state, ch| {
+        match lexer.do_while(state, ch, &|_, ch: char| ch.is_whitespace()) {
+            (end, Some(_)) => Ok(Some((end, token()))),
+            _ => Ok(None),
+        }
+    })
+}
+
+//a Identifier
+//fp identifier
+/// Build a parse function that matches an identifier, which is a
+/// character for which `is_start` returns true followed by any number
+/// of characters for which `is_continue` returns true
+///
+/// The token is constructed by `token` from the text of the
+/// identifier.
+pub fn identifier<'a, L, S, C, F>(
+    is_start: S,
+    is_continue: C,
+    token: F,
+) -> BoxDynLexerParseFn<'a, L>
+where
+    L: Lexer + CharStream<<L as Lexer>::State>,
+    <L as Lexer>::State: PosnInCharStream,
+    S: Fn(char) -> bool + 'a,
+    C: Fn(char) -> bool + 'a,
+    F: Fn(&str) -> <L as Lexer>::Token + 'a,
+{
+    Box::new(move |lexer: &L, state, ch| {
+        let matches = |n, ch| {
+            if n == 0 {
+                is_start(ch)
+            } else {
+                is_continue(ch)
+            }
+        };
+        match lexer.do_while(state, ch, &matches) {
+            (end, Some((start, _))) => Ok(Some((end, token(lexer.get_text(start, end))))),
+            _ => Ok(None),
+        }
+    })
+}
+
+//a Character table
+//fp char_table
+/// Build a parse function that matches a single character from a
+/// table, yielding a clone of the corresponding token
+///
+/// This is useful for punctuation and single-character operators
+pub fn char_table<'a, L>(table: &'a [(char, <L as Lexer>::Token)]) -> BoxDynLexerParseFn<'a, L>
+where
+    L: Lexer + CharStream<<L as Lexer>::State>,
+    <L as Lexer>::State: PosnInCharStream,
+    <L as Lexer>::Token: Clone,
+{
+    Box::new(
+        move |lexer: &L, state, ch| match table.iter().find(|(c, _)| *c == ch) {
+            Some((_, token)) => Ok(Some((lexer.consumed_char(state, ch), token.clone()))),
+            None => Ok(None),
+        },
+    )
+}
@@ -1,11 +1,15 @@
 //a Imports
+mod file_posn;
 mod line_column;
+mod modal_posn;
 mod stream_char_pos;
 mod stream_char_span;
 mod traits;
 
 //a Exports
+pub use file_posn::{FileId, FilePosn};
 pub use line_column::LineColumn;
+pub use modal_posn::ModalPosn;
 pub use stream_char_pos::StreamCharPos;
 pub use stream_char_span::StreamCharSpan;
 pub use traits::{PosnInCharStream, UserPosn};
@@ -0,0 +1,129 @@
+//a Imports
+use crate::{PosnInCharStream, UserPosn};
+
+//a FileId
+//tp FileId
+/// An identifier of a file within a [crate::SourceMap]
+///
+/// This is a small [Copy] value that is carried in a [FilePosn], so
+/// that positions within different files can be distinguished
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FileId(usize);
+
+//ip FileId
+impl FileId {
+    //fp new
+    /// Create a new [FileId] from an index
+    pub fn new(index: usize) -> Self {
+        Self(index)
+    }
+
+    //ap index
+    /// Get the index of the file
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+//ip Display for FileId
+impl std::fmt::Display for FileId {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(fmt, "file {}", self.0)
+    }
+}
+
+//a FilePosn
+//tp FilePosn
+/// A position within one of a number of files, as managed by a
+/// [crate::SourceMap]
+///
+/// This wraps another position type (such as a
+/// [crate::StreamCharPos]) with the [FileId] of the file that the
+/// position is within; the position type is used for the byte
+/// offset, line and column within the file.
+///
+/// As it does not have access to the [crate::SourceMap], its Display
+/// (and error formatting) shows the [FileId] (as `file N`) rather than
+/// the name of the file; use [crate::SourceMap::fmt_location] to
+/// display the name.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct FilePosn<P>
+where
+    P: UserPosn,
+{
+    file: FileId,
+    posn: P,
+}
+
+//ip FilePosn
+impl<P> FilePosn<P>
+where
+    P: UserPosn,
+{
+    //fp new
+    /// Create a new [FilePosn] for a position within a file
+    pub fn new(file: FileId, posn: P) -> Self {
+        Self { file, posn }
+    }
+
+    //ap file
+    /// Get the [FileId] of the file that the position is within
+    pub fn file(&self) -> FileId {
+        self.file
+    }
+
+    //ap posn
+    /// Get the position within the file
+    pub fn posn(&self) -> P {
+        self.posn
+    }
+}
+
+//ip UserPosn for FilePosn
+impl<P> UserPosn for FilePosn<P>
+where
+    P: UserPosn,
+{
+    fn advance_cols(mut self, num_bytes: usize, num_chars: usize) -> Self {
+        self.posn = self.posn.advance_cols(num_bytes, num_chars);
+        self
+    }
+    fn advance_line(mut self, num_bytes: usize) -> Self {
+        self.posn = self.posn.advance_line(num_bytes);
+        self
+    }
+    fn offset(mut self, bytes: isize, lines: isize, columns: isize) -> Option<Self> {
+        self.posn = self.posn.offset(bytes, lines, columns)?;
+        Some(self)
+    }
+    fn line(&self) -> usize {
+        self.posn.line()
+    }
+    fn column(&self) -> usize {
+        self.posn.column()
+    }
+    fn error_fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(fmt, "{} ", self.file)?;
+        self.posn.error_fmt(fmt)
+    }
+}
+
+//ip PosnInCharStream for FilePosn
+impl<P> PosnInCharStream for FilePosn<P>
+where
+    P: PosnInCharStream,
+{
+    fn byte_ofs(&self) -> usize {
+        self.posn.byte_ofs()
+    }
+}
+
+//ip Display for FilePosn
+impl<P> std::fmt::Display for FilePosn<P>
+where
+    P: UserPosn,
+{
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        self.error_fmt(fmt)
+    }
+}
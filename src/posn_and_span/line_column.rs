@@ -45,6 +45,11 @@ impl UserPosn for LineColumn {
         self.line += 1;
         self
     }
+    fn offset(mut self, _: isize, lines: isize, columns: isize) -> Option<Self> {
+        self.line = self.line.checked_add_signed(lines)?;
+        self.column = self.column.checked_add_signed(columns)?;
+        Some(self)
+    }
     fn error_fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         write!(fmt, "line {} column {}", self.line, self.column)
     }
@@ -0,0 +1,95 @@
+//a Imports
+use crate::{PosnInCharStream, UserPosn};
+
+//a ModalPosn
+//tp ModalPosn
+/// A position within the text of a [crate::ModalLexer], together
+/// with the stack of lexer modes in effect at that position
+///
+/// The stack of modes is held by the [crate::ModalLexer]; the
+/// position holds only an identifier of the stack, so that it is
+/// [Copy] and may be saved and restored freely (for backtracking, for
+/// example). The wrapped position type (such as a
+/// [crate::StreamCharPos]) is used for the byte offset, line and
+/// column.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ModalPosn<P>
+where
+    P: UserPosn,
+{
+    posn: P,
+    stack: usize,
+}
+
+//ip ModalPosn
+impl<P> ModalPosn<P>
+where
+    P: UserPosn,
+{
+    //fp new
+    /// Create a new [ModalPosn] for a position with a mode stack
+    /// identifier
+    pub(crate) fn new(posn: P, stack: usize) -> Self {
+        Self { posn, stack }
+    }
+
+    //ap posn
+    /// Get the position within the text
+    pub fn posn(&self) -> P {
+        self.posn
+    }
+
+    //ap stack
+    /// Get the identifier of the mode stack
+    pub fn stack(&self) -> usize {
+        self.stack
+    }
+}
+
+//ip UserPosn for ModalPosn
+impl<P> UserPosn for ModalPosn<P>
+where
+    P: UserPosn,
+{
+    fn advance_cols(mut self, num_bytes: usize, num_chars: usize) -> Self {
+        self.posn = self.posn.advance_cols(num_bytes, num_chars);
+        self
+    }
+    fn advance_line(mut self, num_bytes: usize) -> Self {
+        self.posn = self.posn.advance_line(num_bytes);
+        self
+    }
+    fn offset(mut self, bytes: isize, lines: isize, columns: isize) -> Option<Self> {
+        self.posn = self.posn.offset(bytes, lines, columns)?;
+        Some(self)
+    }
+    fn line(&self) -> usize {
+        self.posn.line()
+    }
+    fn column(&self) -> usize {
+        self.posn.column()
+    }
+    fn error_fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        self.posn.error_fmt(fmt)
+    }
+}
+
+//ip PosnInCharStream for ModalPosn
+impl<P> PosnInCharStream for ModalPosn<P>
+where
+    P: PosnInCharStream,
+{
+    fn byte_ofs(&self) -> usize {
+        self.posn.byte_ofs()
+    }
+}
+
+//ip Display for ModalPosn
+impl<P> std::fmt::Display for ModalPosn<P>
+where
+    P: UserPosn,
+{
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        self.error_fmt(fmt)
+    }
+}
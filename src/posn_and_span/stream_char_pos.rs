@@ -43,6 +43,11 @@ where
         self.pos = self.pos.advance_line(num_bytes);
         self
     }
+    fn offset(mut self, bytes: isize, lines: isize, columns: isize) -> Option<Self> {
+        self.byte_ofs = self.byte_ofs.checked_add_signed(bytes)?;
+        self.pos = self.pos.offset(bytes, lines, columns)?;
+        Some(self)
+    }
     fn line(&self) -> usize {
         self.pos.line()
     }
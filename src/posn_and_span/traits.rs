@@ -40,6 +40,20 @@ pub trait UserPosn:
         self
     }
 
+    /// Get the position moved for an edit of the text that precedes
+    /// it, by a change in the number of bytes and lines before it,
+    /// and a change in its column (which is only non-zero if the
+    /// position is on the line at which the edit ends)
+    ///
+    /// This permits the positions of tokens after an edit to be
+    /// updated without stepping over the text again (see
+    /// [crate::LexerOfString::relex]); the default is None, which
+    /// indicates that this is not supported
+    #[must_use]
+    fn offset(self, _bytes: isize, _lines: isize, _columns: isize) -> Option<Self> {
+        None
+    }
+
     /// Return the line number (if supported, else 0)
     #[must_use]
     fn line(&self) -> usize {
@@ -63,7 +77,11 @@ pub trait UserPosn:
 }
 
 //ip UserPosn for ()
-impl UserPosn for () {}
+impl UserPosn for () {
+    fn offset(self, _bytes: isize, _lines: isize, _columns: isize) -> Option<Self> {
+        Some(())
+    }
+}
 
 //tt PosnInCharStream
 /// Trait for location within a character stream
@@ -87,6 +105,9 @@ impl UserPosn for usize {
     fn advance_line(self, byte_ofs: usize) -> Self {
         self + byte_ofs
     }
+    fn offset(self, bytes: isize, _lines: isize, _columns: isize) -> Option<Self> {
+        self.checked_add_signed(bytes)
+    }
 }
 
 //ip PosnInCharStream for usize
@@ -0,0 +1,202 @@
+//a Imports
+use crate::{ContextStyle, FileId, FilePosn, FmtContext, PosnInCharStream, UserPosn};
+use crate::{LexerError, LexerOfStr, LexerOfString};
+
+//a SourceFile
+//tp SourceFile
+/// A single named file held by a [SourceMap]
+///
+/// This is a [LexerOfString] whose positions are [FilePosn] values
+/// carrying the [FileId] of the file, together with the name (or
+/// path) of the file.
+///
+/// The [FmtContext] implementation precedes the context with a
+/// `path:line:col` header for the start of the region
+#[derive(Debug)]
+pub struct SourceFile<P, T, E>
+where
+    P: PosnInCharStream,
+{
+    id: FileId,
+    name: String,
+    text: LexerOfString<FilePosn<P>, T, E>,
+}
+
+//ip SourceFile
+impl<P, T, E> SourceFile<P, T, E>
+where
+    P: PosnInCharStream,
+    T: Sized + std::fmt::Debug,
+    E: LexerError<FilePosn<P>>,
+{
+    //ap id
+    /// Get the [FileId] of the file
+    pub fn id(&self) -> FileId {
+        self.id
+    }
+
+    //ap name
+    /// Get the name of the file
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    //ap text
+    /// Get a [str] reference to the text content of the file
+    pub fn text(&self) -> &str {
+        self.text.text()
+    }
+
+    //mp start
+    /// Get the position of the start of the file
+    ///
+    /// This is the start state of the lexer returned by
+    /// [SourceFile::lexer], so that all the positions produced are
+    /// tagged with the file's [FileId]
+    pub fn start(&self) -> FilePosn<P> {
+        FilePosn::new(self.id, P::default())
+    }
+
+    //mp lexer
+    /// Create a [LexerOfStr] that will parse the text of the file,
+    /// whose iterators start at [SourceFile::start]
+    pub fn lexer(&self) -> LexerOfStr<'_, FilePosn<P>, T, E> {
+        self.text.lexer().with_start(self.start())
+    }
+}
+
+//ip FmtContext for SourceFile
+impl<P, T, E> FmtContext<FilePosn<P>> for SourceFile<P, T, E>
+where
+    P: PosnInCharStream,
+    T: Sized + std::fmt::Debug,
+    E: LexerError<FilePosn<P>>,
+{
+    fn line_length(&self, line: usize) -> usize {
+        self.text.line_length(line)
+    }
+
+    fn fmt_line(&self, f: &mut dyn std::fmt::Write, line: usize) -> std::fmt::Result {
+        self.text.fmt_line(f, line)
+    }
+
+    fn num_lines(&self) -> Option<usize> {
+        self.text.num_lines()
+    }
+
+    fn fmt_location(&self, f: &mut dyn std::fmt::Write, posn: &FilePosn<P>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.name, posn.line(), posn.column())
+    }
+
+    fn fmt_context_with(
+        &self,
+        fmt: &mut dyn std::fmt::Write,
+        style: &ContextStyle,
+        start: &FilePosn<P>,
+        end: &FilePosn<P>,
+    ) -> std::fmt::Result {
+        self.fmt_location(fmt, start)?;
+        writeln!(fmt)?;
+        self.text.fmt_context_with(fmt, style, start, end)
+    }
+}
+
+//a SourceMap
+//tp SourceMap
+/// A registry of many source files, each of which is given a
+/// distinct [FileId]
+///
+/// Each file is held as a [SourceFile], which can be used to create a
+/// [LexerOfStr] for the file; the lexer starts at
+/// [SourceFile::start], so every position (and hence every token span
+/// and error) identifies the file it came from.
+///
+/// A [FilePosn] only displays the [FileId] of its file, as it does not
+/// have access to the map; the [SourceMap::fmt_location] and
+/// [SourceMap::fmt_context] methods resolve the name of the file, the
+/// latter generating context for a region of any file in the map,
+/// with a `path:line:col` header.
+#[derive(Debug)]
+pub struct SourceMap<P, T, E>
+where
+    P: PosnInCharStream,
+{
+    files: Vec<SourceFile<P, T, E>>,
+}
+
+//ip Default for SourceMap
+impl<P, T, E> Default for SourceMap<P, T, E>
+where
+    P: PosnInCharStream,
+{
+    fn default() -> Self {
+        Self { files: Vec::new() }
+    }
+}
+
+//ip SourceMap
+impl<P, T, E> SourceMap<P, T, E>
+where
+    P: PosnInCharStream,
+    T: Sized + std::fmt::Debug,
+    E: LexerError<FilePosn<P>>,
+{
+    //mp add_file
+    /// Add a file with a name and its text to the map, returning its
+    /// [FileId]
+    pub fn add_file<N: Into<String>, S: Into<String>>(&mut self, name: N, text: S) -> FileId {
+        let id = FileId::new(self.files.len());
+        let text = LexerOfString::default().set_text(text);
+        self.files.push(SourceFile {
+            id,
+            name: name.into(),
+            text,
+        });
+        id
+    }
+
+    //ap file
+    /// Get the [SourceFile] for a [FileId], if it is in the map
+    pub fn file(&self, id: FileId) -> Option<&SourceFile<P, T, E>> {
+        self.files.get(id.index())
+    }
+
+    //ap files
+    /// Iterate over all the files in the map
+    pub fn files(&self) -> impl Iterator<Item = &SourceFile<P, T, E>> {
+        self.files.iter()
+    }
+
+    //mp fmt_location
+    /// Format a position within a file in the map as `path:line:col`
+    ///
+    /// It is an error if the file is not in the map
+    pub fn fmt_location(
+        &self,
+        fmt: &mut dyn std::fmt::Write,
+        posn: &FilePosn<P>,
+    ) -> std::fmt::Result {
+        match self.file(posn.file()) {
+            Some(file) => file.fmt_location(fmt, posn),
+            None => Err(std::fmt::Error),
+        }
+    }
+
+    //mp fmt_context
+    /// Format the context of a region between two positions within a
+    /// file in the map, with a `path:line:col` header
+    ///
+    /// The file is that of the start position; it is an error if the
+    /// file is not in the map
+    pub fn fmt_context(
+        &self,
+        fmt: &mut dyn std::fmt::Write,
+        start: &FilePosn<P>,
+        end: &FilePosn<P>,
+    ) -> std::fmt::Result {
+        match self.file(start.file()) {
+            Some(file) => file.fmt_context(fmt, start, end),
+            None => Err(std::fmt::Error),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+#![cfg(feature = "derive")]
+//a Imports
+use lexer_rs::{Lexer, LexerOfStr, LineColumn, Token};
+use lexer_rs::{SimpleParseError, StreamCharPos, UserPosn};
+
+//a Lexer
+//tp CalcToken
+#[derive(Debug, Clone, Copy, PartialEq, Token)]
+enum CalcToken {
+    #[skip]
+    #[regex("[ \\t\\n]+")]
+    Whitespace,
+    #[token("(")]
+    Open,
+    #[token(")")]
+    Close,
+    #[token("+")]
+    Plus,
+    #[token("let")]
+    Let,
+    #[regex("[a-z]+")]
+    Id,
+    #[regex("\\d+\\.\\d+")]
+    Value(f64),
+    #[regex("\\d+")]
+    Int(u8),
+}
+
+//mi punct
+/// Tokens with only unit variants, derived where 'matches' and
+/// 'unreachable' are shadowed
+mod punct {
+    #[allow(unused_macros)]
+    macro_rules! matches {
+        ($($t:tt)*) => {
+            compile_error!("the derived code must not use this macro")
+        };
+    }
+
+    #[allow(unused_macros)]
+    macro_rules! unreachable {
+        ($($t:tt)*) => {
+            compile_error!("the derived code must not use this macro")
+        };
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, lexer_rs::Token)]
+    pub enum Punct {
+        #[skip]
+        #[regex(" +")]
+        Space,
+        #[token(",")]
+        Comma,
+        #[token("..")]
+        Range,
+    }
+}
+
+type TextPos = StreamCharPos<LineColumn>;
+type LexError = SimpleParseError<TextPos>;
+type TextStream<'a> = LexerOfStr<'a, TextPos, CalcToken, LexError>;
+
+//a Tests
+#[test]
+fn test_derive() {
+    let lexer = TextStream::new("let x (1.5 +\n letter) + 2");
+    let parsers = CalcToken::parsers();
+    let tokens: Vec<_> = lexer
+        .iter(&parsers)
+        .map(|t| t.unwrap())
+        .filter(|t| !t.is_skipped())
+        .collect();
+    assert_eq!(
+        tokens,
+        [
+            CalcToken::Let,
+            CalcToken::Id,
+            CalcToken::Open,
+            CalcToken::Value(1.5),
+            CalcToken::Plus,
+            CalcToken::Id,
+            CalcToken::Close,
+            CalcToken::Plus,
+            CalcToken::Int(2),
+        ]
+    );
+    let text: Vec<_> = tokens.iter().map(|t| t.to_string()).collect();
+    assert_eq!(text, ["let", "Id", "(", "1.5", "+", "Id", ")", "+", "2"]);
+}
+
+#[test]
+fn test_derive_errors() {
+    let parsers = CalcToken::parsers();
+    let lexer = TextStream::new("12 300");
+    let mut iter = lexer.iter(&parsers);
+    assert_eq!(iter.next().unwrap().unwrap(), CalcToken::Int(12));
+    assert!(iter.next().unwrap().unwrap().is_skipped());
+    let e = iter.next().unwrap().unwrap_err();
+    assert_eq!(e.ch, '3');
+    assert_eq!(e.pos.column(), 4);
+}
+
+#[test]
+fn test_derive_unit_variants() {
+    use punct::Punct;
+    let lexer: LexerOfStr<TextPos, Punct, LexError> = LexerOfStr::new(".., ,");
+    let parsers = Punct::parsers();
+    let tokens: Vec<_> = lexer
+        .iter(&parsers)
+        .map(|t| t.unwrap())
+        .filter(|t| !t.is_skipped())
+        .collect();
+    assert_eq!(tokens, [Punct::Range, Punct::Comma, Punct::Comma]);
+}
@@ -0,0 +1,97 @@
+//a Imports
+use lexer_rs::{
+    BoxDynLexerParseFn, CharStream, Dfa, DfaBuilder, DfaLexer, Lexer, LexerOfStr, LineColumn,
+};
+use lexer_rs::{SimpleParseError, StreamCharPos, UserPosn};
+
+//a Lexer
+//tp Token
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Whitespace,
+    If,
+    Id(usize),
+    Int(u64),
+    Arrow,
+    Minus,
+    Bang,
+}
+
+type TextPos = StreamCharPos<LineColumn>;
+type LexError = SimpleParseError<TextPos>;
+type TextStream<'a> = LexerOfStr<'a, TextPos, Token, LexError>;
+type DfaStream<'a, 'd> = DfaLexer<'a, 'd, TextPos, Token, LexError>;
+
+//fi dfa
+fn dfa() -> Dfa<'static, Token> {
+    DfaBuilder::new()
+        .literal("if", 1, |_| Token::If)
+        .literal("->", 0, |_| Token::Arrow)
+        .literal("-", 0, |_| Token::Minus)
+        .pattern("[a-zA-Z_]\\w*", 0, |s| Token::Id(s.len()))
+        .unwrap()
+        .pattern("\\d+", 0, |s| Token::Int(s.parse().unwrap()))
+        .unwrap()
+        .pattern("(\\s)+", 0, |_| Token::Whitespace)
+        .unwrap()
+        .build()
+}
+
+//a Tests
+#[test]
+fn test_dfa_lexer() {
+    let dfa = dfa();
+    let lexer = DfaStream::new("if iffy->-12\n  é x_1 !", &dfa);
+    let parsers = [Box::new(|_lexer: &DfaStream, state: TextPos, ch| {
+        Ok((ch == '!').then(|| (state.advance_cols(1, 1), Token::Bang)))
+    }) as BoxDynLexerParseFn<DfaStream>];
+    let mut tokens = Vec::new();
+    let mut state = TextPos::default();
+    loop {
+        match lexer.parse(state, &parsers) {
+            Ok(Some((next, Token::Whitespace))) => state = next,
+            Ok(Some((next, t))) => {
+                tokens.push(t);
+                state = next;
+            }
+            Ok(None) => break,
+            Err(e) => {
+                assert_eq!(e.ch, 'é');
+                assert_eq!(e.pos.line(), 2);
+                assert_eq!(e.pos.column(), 3);
+                state = lexer.consumed_char(e.pos, e.ch);
+            }
+        }
+    }
+    assert_eq!(
+        tokens,
+        [
+            Token::If,
+            Token::Id(4),
+            Token::Arrow,
+            Token::Minus,
+            Token::Int(12),
+            Token::Id(3),
+            Token::Bang,
+        ]
+    );
+}
+
+#[test]
+fn test_dfa_parse_fn() {
+    let dfa = dfa();
+    let lexer = TextStream::new("x->if");
+    let parsers = [dfa.parse_fn()];
+    let tokens: Vec<_> = lexer.iter(&parsers).map(|t| t.unwrap()).collect();
+    assert_eq!(tokens, [Token::Id(1), Token::Arrow, Token::If]);
+}
+
+#[test]
+fn test_dfa_errors() {
+    for (pattern, position) in [("a(b", 3), ("*a", 0), ("[a-", 3), ("a)", 1), ("\\q", 1)] {
+        let e = DfaBuilder::new()
+            .pattern(pattern, 0, |_| Token::Whitespace)
+            .unwrap_err();
+        assert_eq!(e.position(), position, "{}", e);
+    }
+}
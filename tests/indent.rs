@@ -0,0 +1,114 @@
+//a Imports
+use lexer_rs::{parsers, BoxDynLexerParseFn, IndentToken, Lexer, LexerOfStr, LineColumn};
+use lexer_rs::{SimpleParseError, SimpleParseErrorKind, StreamCharPos, UserPosn};
+
+//a Lexer
+//tp Token
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Whitespace,
+    Comment,
+    Id(usize),
+    Colon,
+}
+
+type TextPos = StreamCharPos<LineColumn>;
+type LexError = SimpleParseError<TextPos>;
+type TextStream<'a> = LexerOfStr<'a, TextPos, Token, LexError>;
+type ByteStream<'a> = LexerOfStr<'a, usize, Token, SimpleParseError<usize>>;
+
+//fi parsers
+fn parsers<'a>() -> Vec<BoxDynLexerParseFn<'a, TextStream<'a>>> {
+    vec![
+        parsers::whitespace(|| Token::Whitespace),
+        parsers::line_comment("#", |_| Token::Comment),
+        parsers::identifier(
+            |c| c.is_alphabetic(),
+            |c| c.is_alphanumeric(),
+            |s| Token::Id(s.len()),
+        ),
+        parsers::char_table(&[(':', Token::Colon)]),
+    ]
+}
+
+//fi is_trivia
+fn is_trivia(t: &Token) -> bool {
+    matches!(t, Token::Whitespace | Token::Comment)
+}
+
+//a Tests
+#[test]
+fn test_indent() {
+    use IndentToken::*;
+    let lexer = TextStream::new("a:\n  bb\n\n    # comment\n  c:\n    d\ne\n  f");
+    let parsers = parsers();
+    let tokens: Vec<_> = lexer
+        .iter_indented(&parsers, &is_trivia)
+        .map(|t| t.unwrap())
+        .collect();
+    assert_eq!(
+        tokens,
+        [
+            Token(self::Token::Id(1)),
+            Token(self::Token::Colon),
+            Newline,
+            Indent,
+            Token(self::Token::Id(2)),
+            Newline,
+            Token(self::Token::Id(1)),
+            Token(self::Token::Colon),
+            Newline,
+            Indent,
+            Token(self::Token::Id(1)),
+            Newline,
+            Dedent,
+            Dedent,
+            Token(self::Token::Id(1)),
+            Newline,
+            Indent,
+            Token(self::Token::Id(1)),
+            Newline,
+            Dedent,
+        ]
+    );
+}
+
+#[test]
+fn test_bad_indent() {
+    let lexer = TextStream::new("a\n    b\n  c\n");
+    let parsers = parsers();
+    let mut iter = lexer.iter_indented(&parsers, &is_trivia);
+    let tokens: Vec<_> = iter.by_ref().take(4).map(|t| t.unwrap()).collect();
+    assert_eq!(tokens[3], IndentToken::Token(Token::Id(1)));
+    let e = iter.next().unwrap().unwrap_err();
+    assert_eq!(e.kind(), SimpleParseErrorKind::BadIndent);
+    assert_eq!(e.ch, 'c');
+    assert_eq!((e.pos.line(), e.pos.column()), (3, 3));
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn test_indent_untracked() {
+    use IndentToken::*;
+    let parsers: Vec<BoxDynLexerParseFn<ByteStream>> = vec![
+        parsers::whitespace(|| self::Token::Whitespace),
+        parsers::identifier(
+            |c| c.is_alphabetic(),
+            |c| c.is_alphanumeric(),
+            |s| self::Token::Id(s.len()),
+        ),
+    ];
+    let lexer = ByteStream::new("a\n b");
+    let tokens: Vec<_> = lexer
+        .iter_indented(&parsers, &is_trivia)
+        .map(|t| t.unwrap())
+        .collect();
+    assert_eq!(
+        tokens,
+        [
+            Token(self::Token::Id(1)),
+            Token(self::Token::Id(1)),
+            Newline
+        ]
+    );
+}
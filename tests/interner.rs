@@ -0,0 +1,84 @@
+//a Imports
+use lexer_rs::{BoxDynLexerParseFn, CharStream, Interner, Lexer, LexerOfStr};
+use lexer_rs::{Dfa, DfaBuilder, DfaLexer};
+use lexer_rs::{LexerParseResult, LineColumn, SimpleParseError, StreamCharPos, Symbol};
+
+//a Lexer
+//tp Token
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Whitespace,
+    Id(Symbol),
+}
+
+type TextPos = StreamCharPos<LineColumn>;
+type LexError = SimpleParseError<TextPos>;
+type TextStream<'a> = LexerOfStr<'a, TextPos, Token, LexError>;
+type LexResult = LexerParseResult<TextPos, Token, LexError>;
+
+//fi parse_whitespace_fn
+fn parse_whitespace_fn<L: CharStream<TextPos>>(stream: &L, state: TextPos, ch: char) -> LexResult {
+    match stream.do_while(state, ch, &|_, ch| ch.is_whitespace()) {
+        (end, Some(_)) => Ok(Some((end, Token::Whitespace))),
+        _ => Ok(None),
+    }
+}
+
+//fi parse_id_fn
+fn parse_id_fn<L: CharStream<TextPos>>(stream: &L, state: TextPos, ch: char) -> LexResult {
+    let (end, Some(_)) = stream.do_while(state, ch, &|_, ch| ch.is_ascii_alphabetic()) else {
+        return Ok(None);
+    };
+    let interner = stream.interner().expect("Lexer must have an interner");
+    let symbol = interner.intern(stream.get_text(state, end));
+    Ok(Some((end, Token::Id(symbol))))
+}
+
+//a Tests
+#[test]
+fn test_interned_ids() {
+    let interner = Interner::new();
+    let lexer = TextStream::new("foo bar foo baz bar").with_interner(&interner);
+    let parsers = [
+        Box::new(parse_whitespace_fn) as BoxDynLexerParseFn<TextStream>,
+        Box::new(parse_id_fn),
+    ];
+    let symbols: Vec<Symbol> = lexer
+        .iter(&parsers)
+        .filter_map(|t| match t.unwrap() {
+            Token::Id(s) => Some(s),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(symbols.len(), 5);
+    assert_eq!(symbols[0], symbols[2]);
+    assert_eq!(symbols[1], symbols[4]);
+    assert_ne!(symbols[0], symbols[1]);
+    assert_eq!(interner.len(), 3);
+
+    let names: Vec<String> = symbols
+        .iter()
+        .map(|s| interner.resolve(*s).unwrap().to_string())
+        .collect();
+    assert_eq!(names.join(" "), "foo bar foo baz bar");
+    assert_eq!(interner.get("baz"), Some(symbols[3]));
+    assert_eq!(interner.get("qux"), None);
+}
+
+#[test]
+fn test_dfa_lexer_interner() {
+    let interner = Interner::new();
+    let dfa: Dfa<Token> = DfaBuilder::new().build();
+    let lexer: DfaLexer<TextPos, Token, LexError> =
+        DfaLexer::new("a b a", &dfa).with_interner(&interner);
+    let parsers = [
+        Box::new(parse_whitespace_fn) as BoxDynLexerParseFn<_>,
+        Box::new(parse_id_fn),
+    ];
+    let ids = lexer
+        .iter(&parsers)
+        .filter(|t| matches!(t, Ok(Token::Id(_))))
+        .count();
+    assert_eq!(ids, 3);
+    assert_eq!(interner.len(), 2);
+}
@@ -0,0 +1,465 @@
+//a Imports
+use lexer_rs::{BoxDynLexerParseFn, CharStream, FmtContext, Lexer, LexerOfStr, LexerOfString};
+use lexer_rs::{LexerParseResult, LineColumn, SimpleParseError, StreamCharPos, StreamCharSpan};
+use lexer_rs::{PosnInCharStream, SimpleParseErrorKind, UserPosn};
+
+//a Lexer
+//tp Token
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Whitespace,
+    Id,
+    Semicolon,
+}
+
+type TextPos = StreamCharPos<LineColumn>;
+type LexError = SimpleParseError<TextPos>;
+type TextStream<'a> = LexerOfStr<'a, TextPos, Token, LexError>;
+type LexResult = LexerParseResult<TextPos, Token, LexError>;
+
+//fi parse_char_fn
+fn parse_char_fn<L: CharStream<TextPos>>(stream: &L, state: TextPos, ch: char) -> LexResult {
+    match ch {
+        ';' => Ok(Some((stream.consumed_char(state, ch), Token::Semicolon))),
+        '\n' => Ok(Some((stream.consumed_char(state, ch), Token::Whitespace))),
+        _ => Ok(None),
+    }
+}
+
+//fi parse_id_fn
+fn parse_id_fn<L: CharStream<TextPos>>(stream: &L, state: TextPos, ch: char) -> LexResult {
+    match stream.do_while(state, ch, &|_, ch: char| ch.is_alphabetic()) {
+        (end, Some(_)) => Ok(Some((end, Token::Id))),
+        _ => Ok(None),
+    }
+}
+
+//fi parse_whitespace_fn
+fn parse_whitespace_fn<L: CharStream<TextPos>>(stream: &L, state: TextPos, ch: char) -> LexResult {
+    match stream.do_while(state, ch, &|_, ch: char| ch == ' ' || ch == '\t') {
+        (end, Some(_)) => Ok(Some((end, Token::Whitespace))),
+        _ => Ok(None),
+    }
+}
+
+//fi parsers
+fn parsers<'a, L>() -> [BoxDynLexerParseFn<'a, L>; 3]
+where
+    L: Lexer<Token = Token, State = TextPos, Error = LexError> + CharStream<TextPos> + 'a,
+{
+    [
+        Box::new(parse_char_fn::<L>),
+        Box::new(parse_id_fn::<L>),
+        Box::new(parse_whitespace_fn::<L>),
+    ]
+}
+
+//a Tests
+#[test]
+fn test_iter_spanned() {
+    let text = LexerOfString::default().set_text("let x;\n  fred;");
+    let lexer = text.lexer();
+    let parsers = parsers();
+    let tokens: Vec<_> = lexer
+        .iter_spanned(&parsers)
+        .map(|t| t.unwrap())
+        .filter(|(_, t)| *t != Token::Whitespace)
+        .collect();
+    let ids: Vec<_> = tokens
+        .iter()
+        .map(|(span, t)| (lexer.get_text_span(span), *t))
+        .collect();
+    assert_eq!(
+        ids,
+        [
+            ("let", Token::Id),
+            ("x", Token::Id),
+            (";", Token::Semicolon),
+            ("fred", Token::Id),
+            (";", Token::Semicolon),
+        ]
+    );
+    let (span, _) = tokens[3];
+    assert_eq!(span.byte_range(), 9..13);
+    assert_eq!(span.start().pos().to_string(), "line 2 column 3");
+    let mut s = String::new();
+    text.fmt_context(&mut s, span.start(), span.end()).unwrap();
+    assert_eq!(s, "    |  let x;\n   2|    fred;\n    |    ^^^^\n    |  \n");
+}
+
+#[test]
+fn test_diagnostic() {
+    use lexer_rs::Diagnostic;
+    let text = LexerOfString::default().set_text("let x;\n\n  fred 1;\n");
+    let lexer = text.lexer();
+    let parsers = parsers();
+    let mut tokens = Vec::new();
+    let mut error = None;
+    for t in lexer.iter_spanned(&parsers) {
+        match t {
+            Ok(t) => tokens.push(t),
+            Err(e) => error = Some(e),
+        }
+    }
+    let (let_span, _) = tokens[0];
+    let (fred_span, _) = tokens[7];
+    assert_eq!(lexer.get_text_span(&fred_span), "fred");
+
+    let diagnostic = Diagnostic::from(error.unwrap())
+        .with_label(*fred_span.start(), *fred_span.end(), "after this")
+        .with_label(*let_span.start(), *fred_span.end(), "in this statement")
+        .with_note("identifiers are alphabetic")
+        .with_help("remove the '1'");
+    let mut s = String::new();
+    diagnostic.fmt_with(&mut s, &text).unwrap();
+    assert_eq!(
+        s,
+        "error: unexpected character '1'
+    --> 3:8
+    |
+   1|  let x;
+    |  ------
+    |  
+   3|    fred 1;
+    |  ------ in this statement
+    |    ---- after this
+    |         ^
+    |
+    = note: identifiers are alphabetic
+    = help: remove the '1'
+"
+    );
+
+    let style = lexer_rs::ContextStyle::default().with_color(true);
+    let mut colored = String::new();
+    diagnostic
+        .fmt_with_style(&mut colored, &text, &style)
+        .unwrap();
+    assert!(colored.starts_with("\x1b[1;31merror\x1b[0m\x1b[1m: unexpected"));
+    assert!(colored.contains("\x1b[1;34m   3|\x1b[0m    fred 1;\n"));
+    let mut stripped = String::new();
+    for (i, part) in colored.split('\x1b').enumerate() {
+        let part = if i == 0 {
+            part
+        } else {
+            part.split_once('m').unwrap().1
+        };
+        stripped.push_str(part);
+    }
+    assert_eq!(stripped, s);
+}
+
+#[test]
+fn test_context_style() {
+    use lexer_rs::ContextStyle;
+    let text: LexerOfString<TextPos, Token, LexError> =
+        LexerOfString::default().set_text("a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n\tk;\nl");
+    let lexer = text.lexer();
+    let parsers = parsers();
+    let tokens: Vec<_> = lexer
+        .iter_spanned(&parsers)
+        .map(|t| t.unwrap())
+        .filter(|(_, t)| *t == Token::Id)
+        .map(|(span, _)| span)
+        .collect();
+
+    let style = ContextStyle {
+        lines_before: 2,
+        lines_after: 5,
+        min_gutter_width: 0,
+        marker: '~',
+        tab_width: 4,
+        ..Default::default()
+    };
+    let mut s = String::new();
+    let k = &tokens[10];
+    text.fmt_context_with(&mut s, &style, k.start(), k.end())
+        .unwrap();
+    assert_eq!(
+        s,
+        "  |  i\n  |  j\n11|      k;\n  |      ~\n  |  l\n  |  \n"
+    );
+
+    let style = ContextStyle {
+        lines_before: 0,
+        elide_head: 2,
+        elide_tail: 1,
+        ..Default::default()
+    };
+    let mut s = String::new();
+    text.fmt_context_with(&mut s, &style, tokens[1].start(), tokens[5].end())
+        .unwrap();
+    assert_eq!(s, "   2|  b\n   3|  c\n    |...\n    |  g\n");
+}
+
+#[test]
+fn test_context_default() {
+    let text: LexerOfString<TextPos, Token, LexError> =
+        LexerOfString::default().set_text("a\nbb\nccc\ndddd\neeeee\nf\ngg\nhhh\niiii\njjjjj\nk\nl");
+    let lexer = text.lexer();
+    let posn = |n| lexer.consumed(TextPos::default(), n);
+    let context = |start, end| {
+        let mut s = String::new();
+        text.fmt_context(&mut s, &posn(start), &posn(end)).unwrap();
+        s
+    };
+    assert_eq!(
+        context(3, 8),
+        "    |  a\n   2|  bb\n   3|  ccc\n    |  dddd\n"
+    );
+    assert_eq!(
+        context(0, 9),
+        "   1|  a\n   2|  bb\n   3|  ccc\n   4|  dddd\n    |  eeeee\n"
+    );
+    assert_eq!(
+        context(3, 30),
+        "    |  a\n   2|  bb\n   3|  ccc\n   4|  dddd\n    |...\n   8|  hhh\n   9|  iiii\n    |  jjjjj\n"
+    );
+    assert_eq!(context(30, 3), "    |  hhh\n");
+}
+
+#[test]
+fn test_recovery() {
+    use lexer_rs::LexerRecovery;
+    let lexer = TextStream::new("let 1x;\n  2 3 4;y");
+    let parsers = parsers();
+
+    let errors = |recovery| {
+        lexer
+            .iter_recovering(&parsers, recovery)
+            .take(20)
+            .filter_map(|t| t.err())
+            .map(|e| (e.ch, e.pos.line(), e.pos.column()))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        errors(LexerRecovery::SkipChar),
+        [('1', 1, 5), ('2', 2, 3), ('3', 2, 5), ('4', 2, 7)]
+    );
+    assert_eq!(
+        errors(LexerRecovery::SkipTo(&[';'])),
+        [('1', 1, 5), ('2', 2, 3)]
+    );
+    let skip_digits = |lexer: &TextStream, state| {
+        let ch = lexer.peek_at(&state)?;
+        match lexer.do_while(state, ch, &|_, ch: char| ch.is_ascii_digit() || ch == ' ') {
+            (state, Some(_)) => Some(state),
+            _ => None,
+        }
+    };
+    assert_eq!(
+        errors(LexerRecovery::Custom(Box::new(skip_digits))),
+        [('1', 1, 5), ('2', 2, 3)]
+    );
+
+    let tokens: Vec<_> = lexer
+        .iter_recovering(&parsers, LexerRecovery::SkipTo(&[';']))
+        .filter_map(|t| t.ok())
+        .filter(|t| *t != Token::Whitespace)
+        .collect();
+    assert_eq!(
+        tokens,
+        [Token::Id, Token::Semicolon, Token::Semicolon, Token::Id]
+    );
+}
+
+#[test]
+fn test_fused_after_error() {
+    use lexer_rs::{LexerRecovery, ParserIterator, PosnInCharStream};
+    let lexer = TextStream::new("ab 1c;");
+    let parsers = parsers();
+
+    let mut iter = ParserIterator::new(&lexer, TextPos::default(), &parsers);
+    assert_eq!(iter.next().unwrap().unwrap(), Token::Id);
+    assert_eq!(iter.next().unwrap().unwrap(), Token::Whitespace);
+    assert_eq!(iter.state().byte_ofs(), 3);
+    assert_eq!(iter.next().unwrap().unwrap_err().ch, '1');
+    assert!(iter.next().is_none());
+    assert!(iter.next().is_none());
+    assert_eq!(iter.state().byte_ofs(), 3);
+
+    let mut iter = ParserIterator::new(&lexer, TextPos::default(), &parsers)
+        .with_recovery(LexerRecovery::SkipChar);
+    assert_eq!(iter.nth(2).unwrap().unwrap_err().ch, '1');
+    assert_eq!(iter.state().byte_ofs(), 4);
+    assert_eq!(iter.next().unwrap().unwrap(), Token::Id);
+    assert_eq!(iter.next().unwrap().unwrap(), Token::Semicolon);
+    assert!(iter.next().is_none());
+    assert!(iter.next().is_none());
+
+    let no_progress = |_: &TextStream, state| Some(state);
+    let mut iter = lexer.iter_recovering(&parsers, LexerRecovery::Custom(Box::new(no_progress)));
+    assert!(iter.nth(2).unwrap().is_err());
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn test_lexer_of_reader() {
+    use lexer_rs::LexerOfReader;
+    type ReaderLexer<'a> = LexerOfReader<std::io::BufReader<&'a [u8]>, TextPos, Token, LexError>;
+
+    let text = "h\u{e9}llo; w\u{f6}rld\n  \u{65e5}\u{672c}\u{8a9e};\t\u{1d49c}x";
+    let str_lexer = TextStream::new(text);
+    let str_parsers = parsers();
+    let expected: Vec<_> = str_lexer
+        .iter_spanned(&str_parsers)
+        .map(|t| t.unwrap())
+        .map(|(span, t)| {
+            (
+                str_lexer.get_text_span(&span).to_string(),
+                span.start().pos(),
+                t,
+            )
+        })
+        .collect();
+
+    // Read in chunks of every size from 1 to 8, so that UTF8
+    // characters are split between reads
+    for capacity in 1..8 {
+        let reader = std::io::BufReader::with_capacity(capacity, text.as_bytes());
+        let lexer = ReaderLexer::new(reader);
+        let parsers = parsers();
+        let tokens: Vec<_> = lexer
+            .iter_spanned(&parsers)
+            .map(|t| t.unwrap())
+            .map(|(span, t)| {
+                (
+                    lexer.get_text_span(&span).to_string(),
+                    span.start().pos(),
+                    t,
+                )
+            })
+            .collect();
+        assert_eq!(tokens, expected);
+    }
+}
+
+#[test]
+fn test_lexer_of_reader_commit() {
+    use lexer_rs::LexerOfReader;
+    type ReaderLexer<'a> = LexerOfReader<std::io::BufReader<&'a [u8]>, TextPos, Token, LexError>;
+
+    let text = "abc def;\n".repeat(10000);
+    let reader = std::io::BufReader::with_capacity(64, text.as_bytes());
+    let mut lexer = ReaderLexer::new(reader);
+    let parsers = parsers();
+    let mut state = TextPos::default();
+    let mut num_tokens = 0;
+    while let Some((next_state, _token)) = lexer.next_token(state, &parsers).unwrap() {
+        assert!(lexer.retained_bytes() < 256);
+        state = next_state;
+        num_tokens += 1;
+    }
+    assert_eq!(num_tokens, 50000);
+    assert_eq!(state.line(), 10001);
+    assert!(lexer.take_error().is_none());
+
+    let reader = std::io::BufReader::with_capacity(4, &b"abc\xff;"[..]);
+    let mut lexer = ReaderLexer::new(reader);
+    let (state, token) = lexer
+        .next_token(TextPos::default(), &parsers)
+        .unwrap()
+        .unwrap();
+    assert_eq!(token, Token::Id);
+    let e = lexer.next_token(state, &parsers).unwrap_err();
+    assert_eq!(e.kind(), SimpleParseErrorKind::ReadError);
+    assert_eq!(e.pos.byte_ofs(), 3);
+    assert_eq!(
+        lexer.take_error().unwrap().kind(),
+        std::io::ErrorKind::InvalidData
+    );
+    assert!(lexer.next_token(state, &parsers).unwrap().is_none());
+}
+
+#[test]
+fn test_lexer_of_reader_iter() {
+    use lexer_rs::LexerOfReader;
+    type ReaderLexer<'a> = LexerOfReader<std::io::BufReader<&'a [u8]>, TextPos, Token, LexError>;
+    let parsers = parsers();
+
+    // An error reading the stream ends the iterator with an error
+    let reader = std::io::BufReader::with_capacity(4, &b"abc \xff;"[..]);
+    let lexer = ReaderLexer::new(reader);
+    let results: Vec<_> = lexer.iter(&parsers).collect();
+    assert_eq!(results.len(), 3);
+    let e = results[2].as_ref().unwrap_err();
+    assert_eq!(e.kind(), SimpleParseErrorKind::ReadError);
+
+    // Data before the committed position may be accessed until the
+    // lexer is compacted
+    let reader = std::io::BufReader::with_capacity(2, &b"abc def"[..]);
+    let mut lexer = ReaderLexer::new(reader);
+    let (end, _) = lexer.parse(TextPos::default(), &parsers).unwrap().unwrap();
+    lexer.commit_consumed(&end);
+    assert_eq!(lexer.get_text(TextPos::default(), end), "abc");
+    assert_eq!(lexer.peek_at(&TextPos::default()), Some('a'));
+    lexer.compact();
+    assert_eq!(lexer.peek_at(&TextPos::default()), None);
+    assert_eq!(lexer.peek_at(&end), Some(' '));
+
+    // The compacting iterator retains only the data beyond the last
+    // token
+    let text = "abc def;\n".repeat(10000);
+    let reader = std::io::BufReader::with_capacity(64, text.as_bytes());
+    let mut lexer = ReaderLexer::new(reader);
+    let parsers = self::parsers();
+    let mut iter = lexer.iter_compacting(&parsers);
+    let mut num_tokens = 0;
+    for t in iter.by_ref() {
+        t.unwrap();
+        num_tokens += 1;
+    }
+    assert_eq!(num_tokens, 50000);
+    assert_eq!(iter.state().line(), 10001);
+    assert!(lexer.retained_bytes() < 256);
+}
+
+#[test]
+fn test_borrowed_tokens() {
+    #[derive(Debug, PartialEq)]
+    enum Borrowed<'a> {
+        Whitespace,
+        Id(&'a str),
+    }
+    type BorrowedStream<'a> = LexerOfStr<'a, TextPos, Borrowed<'a>, LexError>;
+
+    fn parse_id<'a>(
+        stream: &BorrowedStream<'a>,
+        state: TextPos,
+        ch: char,
+    ) -> LexerParseResult<TextPos, Borrowed<'a>, LexError> {
+        match stream.do_while(state, ch, &|_, ch: char| ch.is_alphabetic()) {
+            (end, Some((start, _))) => Ok(Some((end, Borrowed::Id(stream.get_str(start, end))))),
+            _ => Ok(None),
+        }
+    }
+
+    let text = LexerOfString::default().set_text("fred\n jim");
+    let tokens: Vec<_> = {
+        let lexer = text.lexer();
+        let parsers = [
+            Box::new(parse_id) as BoxDynLexerParseFn<BorrowedStream>,
+            Box::new(|stream: &BorrowedStream, state, ch| {
+                match stream.do_while(state, ch, &|_, ch: char| ch.is_whitespace()) {
+                    (end, Some((start, _))) => {
+                        assert_eq!(stream.get_str_span(&StreamCharSpan::new(start, end)), "\n ");
+                        Ok(Some((end, Borrowed::Whitespace)))
+                    }
+                    _ => Ok(None),
+                }
+            }),
+        ];
+        let tokens = lexer.iter(&parsers).map(|t| t.unwrap()).collect();
+        tokens
+    };
+    // The tokens outlive the lexer and parse functions
+    assert_eq!(
+        tokens,
+        [
+            Borrowed::Id("fred"),
+            Borrowed::Whitespace,
+            Borrowed::Id("jim")
+        ]
+    );
+}
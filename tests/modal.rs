@@ -0,0 +1,128 @@
+//a Imports
+use lexer_rs::{parsers, BoxDynLexerParseFn, CharStream, Lexer, LineColumn, ModalLexer};
+use lexer_rs::{ModalPosn, PosnInCharStream, SimpleParseError, StreamCharPos, UserPosn};
+
+//a Lexer
+//tp Token
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Whitespace,
+    Id(usize),
+    Quote,
+    Text(usize),
+    InterpStart,
+    InterpEnd,
+}
+
+type TextPos = ModalPosn<StreamCharPos<LineColumn>>;
+type LexError = SimpleParseError<TextPos>;
+type TextStream<'a> = ModalLexer<'a, StreamCharPos<LineColumn>, Token, LexError>;
+type ParseFn<'a> = BoxDynLexerParseFn<'a, TextStream<'a>>;
+
+/// The mode for the contents of a string
+const STRING: usize = 1;
+
+//fi string_lexer
+/// A lexer of identifiers and strings that may contain `${...}`
+/// interpolations of identifiers and strings
+fn string_lexer(text: &str) -> TextStream<'_> {
+    let code: Vec<ParseFn> = vec![
+        parsers::whitespace(|| Token::Whitespace),
+        parsers::identifier(
+            |c| c.is_alphabetic(),
+            |c| c.is_alphanumeric(),
+            |s| Token::Id(s.len()),
+        ),
+        Box::new(|lexer: &TextStream, state: TextPos, ch| match ch {
+            '"' => {
+                let state = lexer.push_mode(state, STRING);
+                Ok(Some((lexer.consumed_char(state, ch), Token::Quote)))
+            }
+            '}' => Ok(lexer
+                .pop_mode(state)
+                .map(|state| (lexer.consumed_char(state, ch), Token::InterpEnd))),
+            _ => Ok(None),
+        }),
+    ];
+    let string: Vec<ParseFn> = vec![Box::new(|lexer: &TextStream, state: TextPos, ch| {
+        if ch == '"' {
+            let state = lexer.pop_mode(state).unwrap();
+            return Ok(Some((lexer.consumed_char(state, ch), Token::Quote)));
+        }
+        if lexer.matches_str(&state, "${") {
+            let state = lexer.push_mode(state, 0);
+            return Ok(Some((
+                lexer.consumed_ascii_str(state, "${"),
+                Token::InterpStart,
+            )));
+        }
+        let mut end = state;
+        while let Some(c) = lexer.peek_at(&end) {
+            if c == '"' || lexer.matches_str(&end, "${") {
+                break;
+            }
+            end = lexer.consumed_char(end, c);
+        }
+        Ok(Some((end, Token::Text(end.byte_ofs() - state.byte_ofs()))))
+    })];
+    let mut lexer = TextStream::new(text, code);
+    assert_eq!(lexer.add_mode(string), STRING);
+    lexer
+}
+
+//a Tests
+#[test]
+fn test_modal() {
+    let lexer = string_lexer("say \"a ${x \"${yy}z\"}b\" ok");
+    let tokens: Vec<_> = lexer
+        .iter(&[])
+        .map(|t| t.unwrap())
+        .filter(|t| *t != Token::Whitespace)
+        .collect();
+    assert_eq!(
+        tokens,
+        [
+            Token::Id(3),
+            Token::Quote,
+            Token::Text(2),
+            Token::InterpStart,
+            Token::Id(1),
+            Token::Quote,
+            Token::InterpStart,
+            Token::Id(2),
+            Token::InterpEnd,
+            Token::Text(1),
+            Token::Quote,
+            Token::InterpEnd,
+            Token::Text(1),
+            Token::Quote,
+            Token::Id(2),
+        ]
+    );
+}
+
+#[test]
+fn test_modal_state() {
+    let lexer = string_lexer("\"a${b}\" }");
+    let parsers = [];
+    let start = TextPos::default();
+    let (state, _) = lexer.parse(start, &parsers).unwrap().unwrap();
+    assert_eq!(lexer.mode(&state), STRING);
+    let (state, _) = lexer.parse(state, &parsers).unwrap().unwrap();
+    let (inner, _) = lexer.parse(state, &parsers).unwrap().unwrap();
+    assert_eq!(lexer.mode(&inner), 0);
+    assert_eq!(lexer.depth(&inner), 2);
+
+    // Backtracking to a saved state restores its modes
+    let (again, token) = lexer.parse(state, &parsers).unwrap().unwrap();
+    assert_eq!((again, token), (inner, Token::InterpStart));
+
+    let mut state = inner;
+    for _ in 0..4 {
+        state = lexer.parse(state, &parsers).unwrap().unwrap().0;
+    }
+    assert_eq!(lexer.depth(&state), 0);
+    let e = lexer.parse(state, &parsers).unwrap_err();
+    assert_eq!(e.ch, '}');
+    assert_eq!(e.pos.column(), 9);
+}
@@ -0,0 +1,177 @@
+//a Imports
+use lexer_rs::parser_fn;
+use lexer_rs::SimpleParseError;
+use lexer_rs::{BoxDynLexerParseFn, CharStream, Lexer, LexerOfStr, LexerParseResult};
+use lexer_rs::{ParseFnResult, ParseResult, ParserInput, ParserInputResult, ParserInputStream};
+
+//a Lexer
+//tp Token
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Whitespace,
+    Open,
+    Close,
+    Plus,
+    Times,
+    Value(usize),
+}
+
+type TextStream<'a> = LexerOfStr<'a, usize, Token, SimpleParseError<usize>>;
+type LexResult = LexerParseResult<usize, Token, SimpleParseError<usize>>;
+
+//fi parse_char_fn
+fn parse_char_fn(stream: &TextStream, state: usize, ch: char) -> LexResult {
+    let token = match ch {
+        '(' => Token::Open,
+        ')' => Token::Close,
+        '+' => Token::Plus,
+        '*' => Token::Times,
+        _ => return Ok(None),
+    };
+    Ok(Some((stream.consumed_char(state, ch), token)))
+}
+
+//fi parse_value_fn
+fn parse_value_fn(stream: &TextStream, state: usize, ch: char) -> LexResult {
+    match stream.do_while(state, ch, &|_, ch: char| ch.is_ascii_digit()) {
+        (end, Some((start, _n))) => {
+            let value = stream.get_text(start, end).parse().unwrap();
+            Ok(Some((end, Token::Value(value))))
+        }
+        _ => Ok(None),
+    }
+}
+
+//fi parse_whitespace_fn
+fn parse_whitespace_fn(stream: &TextStream, state: usize, ch: char) -> LexResult {
+    match stream.do_while(state, ch, &|_, ch: char| ch.is_whitespace()) {
+        (end, Some(_)) => Ok(Some((end, Token::Whitespace))),
+        _ => Ok(None),
+    }
+}
+
+//a Parser input
+//tp Tokens
+/// A parser input of a slice of tokens from a lexer
+#[derive(Debug, Clone, Copy)]
+struct Tokens<'a>(&'a [Token]);
+
+impl<'a> ParserInput for Tokens<'a> {
+    type Token = Token;
+    type Error = SimpleParseError<usize>;
+    type Stream = Self;
+}
+
+impl<'a> ParserInputStream<Tokens<'a>> for Tokens<'a> {
+    fn get_token(&self) -> ParserInputResult<Tokens<'a>> {
+        Ok(self.0.split_first().map(|(t, rest)| (Tokens(rest), *t)))
+    }
+}
+
+//a Grammar
+type ExprResult<'a> = ParseFnResult<Tokens<'a>, usize>;
+
+//fi value
+fn value(input: Tokens) -> ExprResult {
+    parser_fn::token_map(|t| match t {
+        Token::Value(v) => Some(v),
+        _ => None,
+    })(input)
+}
+
+//fi bracketed
+fn bracketed(input: Tokens) -> ExprResult {
+    parser_fn::delimited(
+        parser_fn::token_matches(|t| t == Token::Open),
+        sum,
+        parser_fn::token_matches(|t| t == Token::Close),
+    )(input)
+}
+
+//fi term
+fn term(input: Tokens) -> ExprResult {
+    let operand = parser_fn::first_of_2(value, bracketed);
+    let times_operand = parser_fn::preceded(
+        parser_fn::token_matches(|t| t == Token::Times),
+        parser_fn::first_of_2(value, bracketed),
+    );
+    parser_fn::fold(0, |_, acc, v| acc * v, operand, times_operand)(input)
+}
+
+//fi sum
+fn sum(input: Tokens) -> ExprResult {
+    let plus_term = parser_fn::preceded(parser_fn::token_matches(|t| t == Token::Plus), term);
+    parser_fn::fold(0, |_, acc, v| acc + v, term, plus_term)(input)
+}
+
+//fi evaluate
+fn evaluate(text: &str) -> Option<usize> {
+    let lexer = TextStream::new(text);
+    let parsers = [
+        Box::new(parse_value_fn) as BoxDynLexerParseFn<TextStream>,
+        Box::new(parse_char_fn),
+        Box::new(parse_whitespace_fn),
+    ];
+    let tokens: Vec<Token> = lexer
+        .iter(&parsers)
+        .map(|t| t.unwrap())
+        .filter(|t| *t != Token::Whitespace)
+        .collect();
+    match sum(Tokens(&tokens)).unwrap() {
+        ParseResult::Matched(rest, v) if rest.0.is_empty() => Some(v),
+        _ => None,
+    }
+}
+
+//a Tests
+#[test]
+fn test_expressions() {
+    assert_eq!(evaluate("3"), Some(3));
+    assert_eq!(evaluate("1 + 2 * 3"), Some(7));
+    assert_eq!(evaluate("(1 + 2) * 3"), Some(9));
+    assert_eq!(evaluate("2 * (3 + 4) * 5 + 1"), Some(71));
+    assert_eq!(evaluate("(1 + 2"), None);
+    assert_eq!(evaluate("1 + + 2"), None);
+}
+
+#[test]
+fn test_list_of() {
+    let tokens = [Token::Value(1), Token::Value(2), Token::Plus];
+    let values = parser_fn::list_of(value, 1..10);
+    match values(Tokens(&tokens)).unwrap() {
+        ParseResult::Matched(rest, v) => {
+            assert_eq!(v, vec![1, 2]);
+            assert_eq!(rest.0, &[Token::Plus]);
+        }
+        _ => panic!("Expected a match"),
+    }
+    let values = parser_fn::list_of(value, 3..10);
+    assert!(matches!(
+        values(Tokens(&tokens)).unwrap(),
+        ParseResult::Mismatched
+    ));
+}
+
+#[test]
+fn test_lexer_token_stream() {
+    use lexer_rs::LexerTokenStream;
+    let lexer = TextStream::new("1 2\n3 + 4");
+    let parsers = [
+        Box::new(parse_value_fn) as BoxDynLexerParseFn<TextStream>,
+        Box::new(parse_char_fn),
+        Box::new(parse_whitespace_fn),
+    ];
+    let is_whitespace = |t: &Token| *t == Token::Whitespace;
+    let input = LexerTokenStream::new(&lexer, &parsers).skip_trivia(&is_whitespace);
+    let values = parser_fn::token_count(|t| matches!(t, Token::Value(_)), 0..10);
+    match values(input).unwrap() {
+        ParseResult::Matched(rest, n) => {
+            assert_eq!(n, 3);
+            assert_eq!(rest.state(), 5);
+            let (rest, t) = rest.get_token().unwrap().unwrap();
+            assert_eq!(t, Token::Plus);
+            assert_eq!(rest.state(), 7);
+        }
+        _ => panic!("Expected a match"),
+    }
+}
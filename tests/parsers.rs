@@ -0,0 +1,318 @@
+//a Imports
+use lexer_rs::{parsers, BoxDynLexerParseFn, Lexer, LexerOfStr, LineColumn};
+use lexer_rs::{SimpleParseError, StreamCharPos, UserPosn};
+
+//a Lexer
+//tp Token
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Whitespace,
+    Id(usize),
+    Int(u64),
+    Float(f64),
+    Comma,
+    Dot,
+}
+
+type TextPos = StreamCharPos<LineColumn>;
+type LexError = SimpleParseError<TextPos>;
+type TextStream<'a> = LexerOfStr<'a, TextPos, Token, LexError>;
+
+//fi parsers
+fn parsers<'a>() -> Vec<BoxDynLexerParseFn<'a, TextStream<'a>>> {
+    vec![
+        parsers::whitespace(|| Token::Whitespace),
+        parsers::identifier(
+            |c| c.is_alphabetic() || c == '_',
+            |c| c.is_alphanumeric() || c == '_',
+            |s| Token::Id(s.len()),
+        ),
+        parsers::float(Token::Float),
+        parsers::integer(Token::Int),
+        parsers::char_table(&[(',', Token::Comma), ('.', Token::Dot)]),
+    ]
+}
+
+//a Tests
+#[test]
+fn test_parsers() {
+    let lexer = TextStream::new("x_1, 0x1F 0o17\n 0b101 12.5e-1 7.x 3E2 0b2");
+    let parsers = parsers();
+    let tokens: Vec<_> = lexer
+        .iter(&parsers)
+        .map(|t| t.unwrap())
+        .filter(|t| *t != Token::Whitespace)
+        .collect();
+    assert_eq!(
+        tokens,
+        [
+            Token::Id(3),
+            Token::Comma,
+            Token::Int(31),
+            Token::Int(15),
+            Token::Int(5),
+            Token::Float(1.25),
+            Token::Int(7),
+            Token::Dot,
+            Token::Id(1),
+            Token::Float(300.0),
+            Token::Int(0),
+            Token::Id(2),
+        ]
+    );
+}
+
+#[test]
+fn test_parsers_positions() {
+    let lexer = TextStream::new("a \n\n  b 99999999999999999999");
+    let parsers = parsers();
+    let mut iter = lexer.iter_spanned(&parsers);
+    let (span, _) = iter.nth(2).unwrap().unwrap();
+    assert_eq!(span.start().pos().line(), 3);
+    assert_eq!(span.start().pos().column(), 3);
+    let e = iter.nth(1).unwrap().unwrap_err();
+    assert_eq!((e.pos.line(), e.pos.column()), (3, 5));
+}
+
+#[test]
+fn test_literals() {
+    use lexer_rs::SimpleParseErrorKind;
+    use std::cell::RefCell;
+
+    let strings = RefCell::new(Vec::new());
+    let parsers: Vec<BoxDynLexerParseFn<TextStream>> = vec![
+        parsers::whitespace(|| Token::Whitespace),
+        parsers::raw_string(|s| {
+            strings.borrow_mut().push(s.into_owned());
+            Token::Dot
+        }),
+        parsers::string('"', |s| {
+            strings.borrow_mut().push(s.into_owned());
+            Token::Comma
+        }),
+        parsers::char_literal(|c| Token::Int(c as u64)),
+        parsers::identifier(char::is_alphabetic, char::is_alphanumeric, |s| {
+            Token::Id(s.len())
+        }),
+    ];
+
+    let text = r##""plain" "a\tb\\\"\x41\u{1F600}" r#"raw "x"
+"# '\n' 'z' "two
+lines" x"##;
+    let lexer = TextStream::new(text);
+    let tokens: Vec<_> = lexer
+        .iter_spanned(&parsers)
+        .map(|t| t.unwrap())
+        .filter(|(_, t)| *t != Token::Whitespace)
+        .collect();
+    assert_eq!(
+        *strings.borrow(),
+        ["plain", "a\tb\\\"A\u{1F600}", "raw \"x\"\n", "two\nlines"]
+    );
+    assert_eq!(tokens[3].1, Token::Int(10));
+    assert_eq!(tokens[4].1, Token::Int(122));
+    let (span, _) = tokens[6];
+    assert_eq!(span.start().pos().line(), 3);
+    assert_eq!(span.start().pos().column(), 8);
+
+    let error = |text| {
+        TextStream::new(text)
+            .iter(&parsers)
+            .find_map(|t| t.err())
+            .unwrap()
+    };
+    let e = error("ab \"xy\\q\"");
+    assert_eq!(e.kind(), SimpleParseErrorKind::BadEscape);
+    assert_eq!((e.ch, e.pos.column()), ('q', 7));
+    let e = error("\"\\x80\"");
+    assert_eq!(e.kind(), SimpleParseErrorKind::BadEscape);
+    assert_eq!((e.ch, e.pos.column()), ('x', 2));
+    let e = error("ab\n \"xy\\u{110000}\"");
+    assert_eq!(e.kind(), SimpleParseErrorKind::BadEscape);
+    assert_eq!((e.ch, e.pos.line(), e.pos.column()), ('u', 2, 5));
+    let e = error("a \"unterminated\nstring");
+    assert_eq!(e.kind(), SimpleParseErrorKind::Unterminated);
+    assert_eq!((e.ch, e.pos.line(), e.pos.column()), ('"', 1, 3));
+    let e = error("'ab'");
+    assert_eq!(e.kind(), SimpleParseErrorKind::FailedToParse);
+    assert_eq!((e.ch, e.pos.column()), ('\'', 1));
+    let e = error("x '\ny'");
+    assert_eq!(e.kind(), SimpleParseErrorKind::Unterminated);
+    assert_eq!((e.ch, e.pos.column()), ('\'', 3));
+    let e = error("'\\n");
+    assert_eq!(e.kind(), SimpleParseErrorKind::Unterminated);
+    assert_eq!((e.ch, e.pos.column()), ('\'', 1));
+}
+
+#[test]
+fn test_char_literal_lifetime() {
+    let parsers: Vec<BoxDynLexerParseFn<TextStream>> = vec![
+        parsers::whitespace(|| Token::Whitespace),
+        parsers::char_literal(|c| Token::Int(c as u64)),
+        parsers::identifier(|c| c == '\'', char::is_alphanumeric, |s| Token::Id(s.len())),
+    ];
+    let lexer = TextStream::new("'a 'b' 'cd\n'\\''");
+    let tokens: Vec<_> = lexer
+        .iter(&parsers)
+        .map(|t| t.unwrap())
+        .filter(|t| *t != Token::Whitespace)
+        .collect();
+    assert_eq!(
+        tokens,
+        [Token::Id(2), Token::Int(98), Token::Id(3), Token::Int(39)]
+    );
+}
+
+#[test]
+fn test_comments() {
+    use lexer_rs::{CharStream, SimpleParseErrorKind};
+    use std::cell::RefCell;
+
+    let docs = RefCell::new(Vec::new());
+    let parsers: Vec<BoxDynLexerParseFn<TextStream>> = vec![
+        parsers::whitespace(|| Token::Whitespace),
+        parsers::line_comment("///", |span| {
+            docs.borrow_mut().push(span);
+            Token::Dot
+        }),
+        parsers::line_comment("//", |_| Token::Whitespace),
+        parsers::line_comment("#", |_| Token::Whitespace),
+        parsers::block_comment("/*", "*/", true, |_| Token::Whitespace),
+        parsers::identifier(char::is_alphabetic, char::is_alphanumeric, |s| {
+            Token::Id(s.len())
+        }),
+    ];
+
+    let text = "/// doc\na // x\n# y\n/* one /* two\n */ */ bc /*\n*/ d";
+    let lexer = TextStream::new(text);
+    let tokens: Vec<_> = lexer
+        .iter_spanned(&parsers)
+        .map(|t| t.unwrap())
+        .filter(|(_, t)| *t != Token::Whitespace)
+        .collect();
+    assert_eq!(lexer.get_text_span(&docs.borrow()[0]), " doc");
+    let ids: Vec<_> = tokens
+        .iter()
+        .map(|(span, t)| (*t, span.start().pos().line(), span.start().pos().column()))
+        .collect();
+    assert_eq!(
+        ids,
+        [
+            (Token::Dot, 1, 1),
+            (Token::Id(1), 2, 1),
+            (Token::Id(2), 5, 8),
+            (Token::Id(1), 6, 4)
+        ]
+    );
+
+    let e = TextStream::new("a\n  /* /* */ b")
+        .iter(&parsers)
+        .find_map(|t| t.err())
+        .unwrap();
+    assert_eq!(e.kind(), SimpleParseErrorKind::Unterminated);
+    assert_eq!((e.ch, e.pos.line(), e.pos.column()), ('/', 2, 3));
+}
+
+#[test]
+#[should_panic(expected = "must not be empty")]
+fn test_empty_comment_prefix() {
+    let _: BoxDynLexerParseFn<TextStream> = parsers::line_comment("", |_| Token::Whitespace);
+}
+
+#[test]
+fn test_keyword_table() {
+    use lexer_rs::parsers::KeywordTable;
+
+    let keywords = [
+        ("=", Token::Int(0)),
+        ("==", Token::Int(1)),
+        ("=>", Token::Int(2)),
+        ("if", Token::Int(3)),
+        ("in", Token::Int(4)),
+        ("i", Token::Int(5)),
+    ];
+    let table = KeywordTable::new(keywords).with_word_boundary(true);
+    assert_eq!(table.get("=="), Some(&Token::Int(1)));
+    assert_eq!(table.get("iff"), None);
+
+    let parsers: Vec<BoxDynLexerParseFn<TextStream>> = vec![
+        parsers::whitespace(|| Token::Whitespace),
+        table.parse_fn(),
+        parsers::identifier(char::is_alphabetic, char::is_alphanumeric, |s| {
+            Token::Id(s.len())
+        }),
+        parsers::char_table(&[(',', Token::Comma)]),
+    ];
+    let lexer = TextStream::new("== = => ===if iffy in,i ink");
+    let tokens: Vec<_> = lexer
+        .iter(&parsers)
+        .map(|t| t.unwrap())
+        .filter(|t| *t != Token::Whitespace)
+        .collect();
+    assert_eq!(
+        tokens,
+        [
+            Token::Int(1),
+            Token::Int(0),
+            Token::Int(2),
+            Token::Int(1),
+            Token::Int(0),
+            Token::Int(3),
+            Token::Id(4),
+            Token::Int(4),
+            Token::Comma,
+            Token::Int(5),
+            Token::Id(3),
+        ]
+    );
+}
+
+#[test]
+fn test_owned_tokens() {
+    use lexer_rs::{LexerOfString, TokenCursor};
+    use std::rc::Rc;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Owned {
+        Whitespace,
+        Str(String),
+        Id(Rc<str>),
+        Ints(Vec<u64>),
+    }
+    type OwnedStream<'a> = LexerOfStr<'a, TextPos, Owned, LexError>;
+
+    let text = LexerOfString::default().set_text("name \"a\\tb\" 3");
+    let lexer = text.lexer();
+    let parsers: Vec<BoxDynLexerParseFn<OwnedStream>> = vec![
+        parsers::whitespace(|| Owned::Whitespace),
+        parsers::string('"', |s| Owned::Str(s.into_owned())),
+        parsers::identifier(char::is_alphabetic, char::is_alphanumeric, |s| {
+            Owned::Id(s.into())
+        }),
+        parsers::integer(|n| Owned::Ints(vec![n; n as usize])),
+    ];
+    let tokens: Vec<_> = lexer
+        .iter(&parsers)
+        .map(|t| t.unwrap())
+        .filter(|t| *t != Owned::Whitespace)
+        .collect();
+    assert_eq!(
+        tokens,
+        [
+            Owned::Id("name".into()),
+            Owned::Str("a\tb".into()),
+            Owned::Ints(vec![3, 3, 3]),
+        ]
+    );
+
+    let is_trivia = |t: &Owned| *t == Owned::Whitespace;
+    let mut cursor = TokenCursor::new(&lexer, &parsers).skip_trivia(&is_trivia);
+    cursor.expect(&Owned::Id("name".into())).unwrap();
+    let (_, s) = cursor
+        .expect_map(|t| match t {
+            Owned::Str(s) => Some(s.clone()),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(s, "a\tb");
+}
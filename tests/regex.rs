@@ -0,0 +1,52 @@
+#![cfg(feature = "regex")]
+//a Imports
+use lexer_rs::parsers::RegexRule;
+use lexer_rs::{BoxDynLexerParseFn, Lexer, LexerOfStr, LineColumn};
+use lexer_rs::{SimpleParseError, StreamCharPos, UserPosn};
+
+//a Lexer
+//tp Token
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Whitespace,
+    Id(usize),
+    Pair(u32, u32),
+}
+
+type TextPos = StreamCharPos<LineColumn>;
+type TextStream<'a> = LexerOfStr<'a, TextPos, Token, SimpleParseError<TextPos>>;
+
+//a Tests
+#[test]
+fn test_regex_rule() {
+    let whitespace = RegexRule::new(r"\s+").unwrap();
+    let id = RegexRule::new(r"[a-z]+").unwrap();
+    let pair = RegexRule::new(r"\((\d+),\s*(\d+)\)").unwrap();
+    let empty = RegexRule::new(r"x*").unwrap();
+    assert!(id.captures(" ab").is_none());
+
+    let lexer = TextStream::new("ab (1,\n 22) \n\n  cde 7");
+    let parsers: [BoxDynLexerParseFn<TextStream>; 4] = [
+        empty.parse_fn(|_| Token::Whitespace),
+        whitespace.parse_fn(|_| Token::Whitespace),
+        id.parse_fn(|c| Token::Id(c[0].len())),
+        pair.parse_fn(|c| Token::Pair(c[1].parse().unwrap(), c[2].parse().unwrap())),
+    ];
+    let tokens: Vec<_> = lexer
+        .iter_spanned(&parsers)
+        .take_while(|t| t.is_ok())
+        .map(|t| t.unwrap())
+        .filter(|(_, t)| *t != Token::Whitespace)
+        .map(|(span, t)| (t, span.start().pos().line(), span.start().pos().column()))
+        .collect();
+    assert_eq!(
+        tokens,
+        [
+            (Token::Id(2), 1, 1),
+            (Token::Pair(1, 22), 1, 4),
+            (Token::Id(3), 4, 3)
+        ]
+    );
+    let e = lexer.iter(&parsers).find_map(|t| t.err()).unwrap();
+    assert_eq!((e.ch, e.pos.line(), e.pos.column()), ('7', 4, 7));
+}
@@ -0,0 +1,105 @@
+//a Imports
+use lexer_rs::{parsers, BoxDynLexerParseFn, FmtContext, Lexer, LexerOfStr, LexerOfString};
+use lexer_rs::{LineColumn, SimpleParseError, StreamCharPos, StreamCharSpan, UserPosn};
+
+//a Lexer
+//tp Token
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Whitespace,
+    Id,
+    Semicolon,
+}
+
+type TextPos = StreamCharPos<LineColumn>;
+type LexError = SimpleParseError<TextPos>;
+type TextStream<'a> = LexerOfStr<'a, TextPos, Token, LexError>;
+type TextString = LexerOfString<TextPos, Token, LexError>;
+type Tokens = Vec<(StreamCharSpan<TextPos>, Token)>;
+
+//fi parsers
+fn parsers<'a>() -> Vec<BoxDynLexerParseFn<'a, TextStream<'a>>> {
+    vec![
+        parsers::whitespace(|| Token::Whitespace),
+        parsers::identifier(
+            |c| c.is_alphabetic(),
+            |c| c.is_alphanumeric(),
+            |_| Token::Id,
+        ),
+        parsers::char_table(&[(';', Token::Semicolon)]),
+    ]
+}
+
+//fi lex
+fn lex(text: &TextString) -> Tokens {
+    let lexer = text.lexer();
+    let parsers = parsers();
+    let tokens = lexer.iter_spanned(&parsers).map(|t| t.unwrap()).collect();
+    tokens
+}
+
+//fi summary
+/// The token, byte range, and start and end line and column of each token
+fn summary(tokens: &Tokens) -> Vec<(Token, std::ops::Range<usize>, [usize; 4])> {
+    tokens
+        .iter()
+        .map(|(span, t)| {
+            let (s, e) = (span.start(), span.end());
+            (
+                *t,
+                span.byte_range(),
+                [s.line(), s.column(), e.line(), e.column()],
+            )
+        })
+        .collect()
+}
+
+//fi lines
+/// The lines of the text as found by the [FmtContext]
+fn lines(text: &TextString) -> Vec<(usize, String)> {
+    (1..=text.num_lines().unwrap())
+        .map(|l| {
+            let mut s = String::new();
+            text.fmt_line(&mut s, l).unwrap();
+            (text.line_length(l), s)
+        })
+        .collect()
+}
+
+//a Tests
+#[test]
+fn test_relex() {
+    let mut text = TextString::default().set_text("let x;\n  fred;\n\nbob jim;\nend");
+    let mut tokens = lex(&text);
+    let edits: &[(std::ops::Range<usize>, &str, usize)] = &[
+        (5..5, "yz", 1),      // extend an identifier
+        (10..10, "\n  ", 1),  // split the whitespace with a new line
+        (0..3, "", 1),        // remove the first identifier
+        (7..17, "a b", 5),    // replace across lines
+        (12..12, "q;\nr", 4), // insert tokens and a line
+        (19..22, "", 1),      // remove an identifier
+        (0..0, "é ", 2),      // insert a multi-byte character at the start
+    ];
+    for (range, replacement, num_changed) in edits {
+        let edit = text.edit(range.clone(), replacement);
+        let parsers = parsers();
+        let changed = text.relex(&mut tokens, &edit, &parsers).unwrap();
+        let fresh = TextString::default().set_text(text.text());
+        assert_eq!(summary(&tokens), summary(&lex(&fresh)), "{:?}", text.text());
+        assert_eq!(lines(&text), lines(&fresh));
+        assert_eq!(changed.len(), *num_changed, "{:?}", text.text());
+    }
+}
+
+#[test]
+fn test_relex_error() {
+    let mut text = TextString::default().set_text("ab cd;\nef gh;");
+    let mut tokens = lex(&text);
+    let edit = text.edit(7..7, "$");
+    let parsers = parsers();
+    let e = text.relex(&mut tokens, &edit, &parsers).unwrap_err();
+    assert_eq!((e.ch, e.pos.line(), e.pos.column()), ('$', 2, 1));
+    assert_eq!(tokens.len(), 4);
+    let before = TextString::default().set_text("ab cd;");
+    assert_eq!(summary(&tokens), summary(&lex(&before)));
+}
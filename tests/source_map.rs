@@ -0,0 +1,103 @@
+//a Imports
+use lexer_rs::{BoxDynLexerParseFn, CharStream, FilePosn, Lexer, LexerOfStr, LexerParseResult};
+use lexer_rs::{LineColumn, ParserIterator, SimpleParseError, SourceMap, StreamCharPos};
+use lexer_rs::{PosnInCharStream, UserPosn};
+
+//a Lexer
+//tp Token
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Whitespace,
+    Id,
+}
+
+type TextPos = FilePosn<StreamCharPos<LineColumn>>;
+type LexError = SimpleParseError<TextPos>;
+type TextStream<'a> = LexerOfStr<'a, TextPos, Token, LexError>;
+type LexResult = LexerParseResult<TextPos, Token, LexError>;
+
+//fi parse_id_fn
+fn parse_id_fn(stream: &TextStream, state: TextPos, ch: char) -> LexResult {
+    match stream.do_while(state, ch, &|_, ch: char| ch.is_alphabetic()) {
+        (end, Some(_)) => Ok(Some((end, Token::Id))),
+        _ => Ok(None),
+    }
+}
+
+//fi parse_whitespace_fn
+fn parse_whitespace_fn(stream: &TextStream, state: TextPos, ch: char) -> LexResult {
+    if ch == '\n' || ch == ' ' {
+        Ok(Some((stream.consumed_char(state, ch), Token::Whitespace)))
+    } else {
+        Ok(None)
+    }
+}
+
+//a Tests
+#[test]
+fn test_source_map() {
+    let mut map = SourceMap::<StreamCharPos<LineColumn>, Token, LexError>::default();
+    let main = map.add_file("main.txt", "fred jim\n");
+    let inc = map.add_file("include/defs.txt", "a\nbob 1");
+    assert_ne!(main, inc);
+    assert_eq!(map.files().count(), 2);
+    assert_eq!(map.file(inc).unwrap().name(), "include/defs.txt");
+
+    let file = map.file(inc).unwrap();
+    let lexer = file.lexer();
+    let parsers = [
+        Box::new(parse_id_fn) as BoxDynLexerParseFn<TextStream>,
+        Box::new(parse_whitespace_fn),
+    ];
+    let mut iter = ParserIterator::new(&lexer, file.start(), &parsers);
+    let mut last = file.start();
+    while let Some(t) = iter.next() {
+        match t {
+            Ok(_) => last = iter.state(),
+            Err(e) => {
+                assert_eq!(e.pos.file(), inc);
+                assert_eq!(e.pos.line(), 2);
+                assert_eq!(e.pos.column(), 5);
+                assert_eq!(e.pos.byte_ofs(), 6);
+                let mut s = String::new();
+                map.fmt_context(&mut s, &e.pos, &e.pos).unwrap();
+                assert_eq!(
+                    s,
+                    "include/defs.txt:2:5\n    |  a\n   2|  bob 1\n    |      ^\n    |  \n"
+                );
+            }
+        }
+    }
+    assert_eq!(last.file(), inc);
+    assert_eq!(last.byte_ofs(), 6);
+}
+
+#[test]
+fn test_source_map_iter() {
+    let mut map = SourceMap::<StreamCharPos<LineColumn>, Token, LexError>::default();
+    map.add_file("main.txt", "fred jim\n");
+    let inc = map.add_file("include/defs.txt", "a\nbob 1");
+
+    let file = map.file(inc).unwrap();
+    let lexer = file.lexer();
+    let parsers = [
+        Box::new(parse_id_fn) as BoxDynLexerParseFn<TextStream>,
+        Box::new(parse_whitespace_fn),
+    ];
+    let mut spans = Vec::new();
+    let mut error = None;
+    for t in lexer.iter_spanned(&parsers) {
+        match t {
+            Ok((span, _)) => spans.push(span),
+            Err(e) => error = Some(e),
+        }
+    }
+    assert_eq!(spans.len(), 4);
+    assert!(spans.iter().all(|s| s.start().file() == inc));
+    let e = error.unwrap();
+    assert_eq!(e.pos.file(), inc);
+
+    let mut s = String::new();
+    map.fmt_location(&mut s, &e.pos).unwrap();
+    assert_eq!(s, "include/defs.txt:2:5");
+}
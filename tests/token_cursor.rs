@@ -0,0 +1,96 @@
+//a Imports
+use lexer_rs::{parsers, BoxDynLexerParseFn, LexerOfStr, LineColumn, TokenCursor};
+use lexer_rs::{SimpleParseError, StreamCharPos, TokenCursorError, UserPosn};
+
+//a Lexer
+//tp Token
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Whitespace,
+    Id(usize),
+    Int(u64),
+    Open,
+    Close,
+    Comma,
+}
+
+type TextPos = StreamCharPos<LineColumn>;
+type LexError = SimpleParseError<TextPos>;
+type TextStream<'a> = LexerOfStr<'a, TextPos, Token, LexError>;
+
+//fi parsers
+fn parsers<'a>() -> Vec<BoxDynLexerParseFn<'a, TextStream<'a>>> {
+    vec![
+        parsers::whitespace(|| Token::Whitespace),
+        parsers::identifier(
+            |c| c.is_alphabetic(),
+            |c| c.is_alphanumeric(),
+            |s| Token::Id(s.len()),
+        ),
+        parsers::integer(Token::Int),
+        parsers::char_table(&[('(', Token::Open), (')', Token::Close), (',', Token::Comma)]),
+    ]
+}
+
+//fi is_trivia
+fn is_trivia(t: &Token) -> bool {
+    *t == Token::Whitespace
+}
+
+//a Tests
+#[test]
+fn test_token_cursor() {
+    let lexer = TextStream::new("f (1, 22) g");
+    let parsers = parsers();
+    let mut cursor = TokenCursor::new(&lexer, &parsers).skip_trivia(&is_trivia);
+
+    assert_eq!(cursor.peek_nth(2).unwrap().unwrap().1, Token::Int(1));
+    assert_eq!(cursor.peek().unwrap().unwrap().1, Token::Id(1));
+    let mark = cursor.mark();
+    cursor.expect(&Token::Id(1)).unwrap();
+    let open = cursor.expect(&Token::Open).unwrap();
+    assert_eq!(open.start().column(), 3);
+
+    let mut args = Vec::new();
+    loop {
+        let (_, n) = cursor
+            .expect_map(|t| match t {
+                Token::Int(n) => Some(*n),
+                _ => None,
+            })
+            .unwrap();
+        args.push(n);
+        if cursor.eat(&Token::Comma).unwrap().is_none() {
+            break;
+        }
+    }
+    assert_eq!(args, [1, 22]);
+
+    // An unexpected token is reported with its span, and not consumed
+    let e = cursor.expect(&Token::Comma).unwrap_err();
+    let span = e.span().unwrap();
+    assert_eq!(span.byte_range(), 8..9);
+    assert!(matches!(e, TokenCursorError::Unexpected(_, Token::Close)));
+    cursor.expect(&Token::Close).unwrap();
+    cursor.expect(&Token::Id(1)).unwrap();
+    assert!(cursor.is_at_end().unwrap());
+    let e = cursor.expect(&Token::Close).unwrap_err();
+    assert!(matches!(e, TokenCursorError::EndOfStream(_)));
+    assert_eq!(e.span().unwrap().byte_range(), 11..11);
+
+    // Backtracking to the mark reparses the tokens
+    cursor.reset(mark);
+    assert_eq!(cursor.next_token().unwrap().unwrap().1, Token::Id(1));
+}
+
+#[test]
+fn test_token_cursor_lexer_error() {
+    let lexer = TextStream::new("a ?");
+    let parsers = parsers();
+    let mut cursor = TokenCursor::new(&lexer, &parsers).skip_trivia(&is_trivia);
+    assert_eq!(cursor.next_token().unwrap().unwrap().1, Token::Id(1));
+    assert!(cursor.peek().is_err());
+    let e = cursor.expect(&Token::Comma).unwrap_err();
+    assert!(matches!(e, TokenCursorError::Lexer(_)));
+    assert!(e.span().is_none());
+}
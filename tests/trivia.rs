@@ -0,0 +1,112 @@
+//a Imports
+use lexer_rs::{parsers, BoxDynLexerParseFn, LexerOfStr, LexerOfString, LineColumn};
+use lexer_rs::{SimpleParseError, StreamCharPos, TriviaLexer, TriviaTokens};
+
+//a Lexer
+//tp Token
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Whitespace,
+    Comment,
+    Id,
+    Equals,
+    Semicolon,
+}
+
+type TextPos = StreamCharPos<LineColumn>;
+type LexError = SimpleParseError<TextPos>;
+type TextStream<'a> = LexerOfStr<'a, TextPos, Token, LexError>;
+type TextString = LexerOfString<TextPos, Token, LexError>;
+
+//fi parsers
+fn parsers<'a>() -> Vec<BoxDynLexerParseFn<'a, TextStream<'a>>> {
+    vec![
+        parsers::whitespace(|| Token::Whitespace),
+        parsers::line_comment("//", |_| Token::Comment),
+        parsers::identifier(
+            |c| c.is_alphabetic(),
+            |c| c.is_alphanumeric(),
+            |_| Token::Id,
+        ),
+        parsers::char_table(&[('=', Token::Equals), (';', Token::Semicolon)]),
+    ]
+}
+
+//fi is_trivia
+fn is_trivia(t: &Token) -> bool {
+    matches!(t, Token::Whitespace | Token::Comment)
+}
+
+//fi lex
+fn lex(text: &str) -> TriviaTokens<TextPos, Token> {
+    let text = TextString::default().set_text(text);
+    let lexer = text.lexer();
+    let parsers = parsers();
+    let tokens = TriviaLexer::new(&lexer, &is_trivia).lex(&parsers).unwrap();
+    assert!(tokens.is_lossless(text.text()));
+    assert_eq!(tokens.round_trip(text.text()), text.text());
+    tokens
+}
+
+//a Tests
+#[test]
+fn test_trivia_attached() {
+    let text = "// header\na = b; // trailing\n  c;\n\n// end\n";
+    let tokens = lex(text);
+    let significant: Vec<Token> = tokens.tokens().iter().map(|t| *t.token()).collect();
+    use Token::*;
+    assert_eq!(significant, [Id, Equals, Id, Semicolon, Id, Semicolon]);
+
+    let kinds = |trivia: &[(_, Token)]| trivia.iter().map(|(_, t)| *t).collect::<Vec<_>>();
+    let t = &tokens.tokens();
+    assert_eq!(kinds(t[0].leading()), [Comment, Whitespace]);
+    assert_eq!(kinds(t[0].trailing()), [Whitespace]);
+    assert_eq!(kinds(t[3].trailing()), [Whitespace, Comment]);
+    assert_eq!(kinds(t[4].leading()), [Whitespace]);
+    assert!(t[5].trailing().is_empty());
+    assert_eq!(
+        kinds(tokens.end_trivia()),
+        [Whitespace, Comment, Whitespace]
+    );
+
+    let full = t[3].full_span().byte_range();
+    assert_eq!(&text[full], "; // trailing");
+}
+
+#[test]
+fn test_round_trip() {
+    for text in [
+        "",
+        "   ",
+        "a",
+        "a;\n",
+        "\n\n  a  =  b ;  \n\t// c\n",
+        "x// y",
+    ] {
+        lex(text);
+    }
+}
+
+#[test]
+fn test_trivia_untracked_lines() {
+    type ByteStream<'a> = LexerOfStr<'a, usize, Token, SimpleParseError<usize>>;
+    let text = "a;\n// comment\nb;";
+    let lexer = ByteStream::new(text);
+    let parsers: Vec<BoxDynLexerParseFn<ByteStream>> = vec![
+        parsers::whitespace(|| Token::Whitespace),
+        parsers::line_comment("//", |_| Token::Comment),
+        parsers::identifier(
+            |c| c.is_alphabetic(),
+            |c| c.is_alphanumeric(),
+            |_| Token::Id,
+        ),
+        parsers::char_table(&[(';', Token::Semicolon)]),
+    ];
+    let tokens = TriviaLexer::new(&lexer, &is_trivia).lex(&parsers).unwrap();
+    assert!(tokens.is_lossless(text));
+
+    // Without lines, all the trivia trail the token before them
+    let semicolon = &tokens.tokens()[1];
+    assert_eq!(semicolon.trailing().len(), 3);
+    assert!(tokens.tokens()[2].leading().is_empty());
+}